@@ -0,0 +1,53 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use serde::{Serialize, Serializer};
+
+/// A parameter value which is computed only when it is serialized.
+///
+/// Created by the [`lazy`] function.
+pub struct Lazy<F>(F);
+
+/// Returns a parameter value which is computed by calling `f` when it is serialized.
+///
+/// The log macros skip records above the maximum log level without evaluating their parameters, and loggers only
+/// serialize the parameters of records they actually write. Wrapping an expensive parameter in `lazy` defers its
+/// computation to that point as well. Note that a logger may serialize a parameter more than once, in which case `f`
+/// will be called each time.
+///
+/// # Examples
+///
+/// ```
+/// # fn expensive_summary() -> String { String::new() }
+/// witchcraft_log::debug!("state dump", unsafe: { summary: witchcraft_log::lazy(expensive_summary) });
+/// ```
+pub fn lazy<F, T>(f: F) -> Lazy<F>
+where
+    F: Fn() -> T,
+    T: Serialize,
+{
+    Lazy(f)
+}
+
+impl<F, T> Serialize for Lazy<F>
+where
+    F: Fn() -> T,
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (self.0)().serialize(serializer)
+    }
+}
@@ -60,18 +60,29 @@
 //! }
 //! ```
 //!
+//! # Compile time filters
+//!
+//! Like the `log` crate, log levels can be statically disabled with Cargo features, in which case the logging macros
+//! compile to nothing for those levels. See [`STATIC_MAX_LEVEL`] for details.
+//!
+//! The crate's own tests log at the info level and above, so they do not support features that disable the info level.
+//! In particular, `cargo test --all-features` enables `max_level_off` and is not supported.
+//!
 //! # Bridging
 //!
 //! Even when an application is using `witchcraft-log`, many of its dependencies may still use the `log` crate. The
-//! `bridge` module provides functionality to forward records from the `log` crate to `witchcraft-log`.
+//! `bridge` module provides functionality to forward records from the `log` crate to `witchcraft-log`. Similarly, the
+//! `slog` module (enabled by the `slog` Cargo feature) provides a `slog::Drain` which forwards to `witchcraft-log`.
 #![doc(html_root_url = "https://docs.rs/witchcraft-log/0.3")]
 #![warn(missing_docs)]
 
+pub use crate::lazy::*;
 pub use crate::level::*;
 pub use crate::logger::*;
 pub use crate::record::*;
 
 pub mod bridge;
+mod lazy;
 mod level;
 mod logger;
 #[macro_use]
@@ -79,6 +90,8 @@ mod macros;
 #[doc(hidden)]
 pub mod private;
 mod record;
+#[cfg(feature = "slog")]
+pub mod slog;
 
 #[cfg(test)]
 mod test;
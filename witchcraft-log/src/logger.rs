@@ -77,6 +77,56 @@ impl Error for SetLoggerError {}
 
 static MAX_LOG_LEVEL_FILTER: AtomicUsize = AtomicUsize::new(0);
 
+/// The statically resolved maximum log level.
+///
+/// Messages logged at a higher level than this are compiled out of the logging macros entirely. It is configured with
+/// Cargo features:
+///
+/// * `max_level_off`, `max_level_fatal`, `max_level_error`, `max_level_warn`, `max_level_info`, `max_level_debug` and
+///   `max_level_trace` set the level for all builds.
+/// * `release_max_level_off`, `release_max_level_fatal`, etc. set the level for builds without `debug_assertions`,
+///   taking precedence over the `max_level_*` features.
+///
+/// If multiple features of the same kind are enabled, the most restrictive wins. With no features enabled, it is
+/// `LevelFilter::Trace`. These features are intended for executables - libraries should not enable them.
+pub const STATIC_MAX_LEVEL: LevelFilter = static_max_level();
+
+const fn static_max_level() -> LevelFilter {
+    if !cfg!(debug_assertions) {
+        if cfg!(feature = "release_max_level_off") {
+            return LevelFilter::Off;
+        } else if cfg!(feature = "release_max_level_fatal") {
+            return LevelFilter::Fatal;
+        } else if cfg!(feature = "release_max_level_error") {
+            return LevelFilter::Error;
+        } else if cfg!(feature = "release_max_level_warn") {
+            return LevelFilter::Warn;
+        } else if cfg!(feature = "release_max_level_info") {
+            return LevelFilter::Info;
+        } else if cfg!(feature = "release_max_level_debug") {
+            return LevelFilter::Debug;
+        } else if cfg!(feature = "release_max_level_trace") {
+            return LevelFilter::Trace;
+        }
+    }
+
+    if cfg!(feature = "max_level_off") {
+        LevelFilter::Off
+    } else if cfg!(feature = "max_level_fatal") {
+        LevelFilter::Fatal
+    } else if cfg!(feature = "max_level_error") {
+        LevelFilter::Error
+    } else if cfg!(feature = "max_level_warn") {
+        LevelFilter::Warn
+    } else if cfg!(feature = "max_level_info") {
+        LevelFilter::Info
+    } else if cfg!(feature = "max_level_debug") {
+        LevelFilter::Debug
+    } else {
+        LevelFilter::Trace
+    }
+}
+
 /// Sets the global maximum log level.
 ///
 /// Generally, this should only be called by the logging implementation.
@@ -87,7 +137,8 @@ pub fn set_max_level(level: LevelFilter) {
 /// Returns the current maximum log level.
 ///
 /// The log macros check this value and discard any message logged at a higher level as an optimization. The maximum
-/// level is set by the `set_max_level` function.
+/// level is set by the `set_max_level` function. Messages logged at a higher level than [`STATIC_MAX_LEVEL`] are
+/// discarded regardless of this value.
 #[inline(always)]
 pub fn max_level() -> LevelFilter {
     unsafe { mem::transmute(MAX_LOG_LEVEL_FILTER.load(Ordering::Relaxed)) }
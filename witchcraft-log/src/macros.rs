@@ -13,18 +13,37 @@
 // limitations under the License.
 
 /// Logs a message at the specified level.
+///
+/// The record's target defaults to the module path of the caller, but can be overridden with a leading `target:`
+/// argument. This applies to all of the logging macros:
+///
+/// ```
+/// witchcraft_log::info!(target: "sync", "finished sync", safe: { files: 15 });
+/// ```
+///
+/// Parameter keys must be unique across the safe and unsafe parameters of a record. Duplicates are rejected at compile
+/// time:
+///
+/// ```compile_fail
+/// witchcraft_log::info!("finished sync", safe: { files: 15, files: 16 });
+/// ```
+///
+/// ```compile_fail
+/// witchcraft_log::info!("finished sync", safe: { files: 15 }, unsafe: { files: 16 });
+/// ```
 #[macro_export]
 macro_rules! log {
-    ($lvl:expr, $msg:expr) => {{
+    (target: $target:expr, $lvl:expr, $msg:expr) => {{
         let level = $lvl;
-        if level <= $crate::max_level() {
+        if level <= $crate::STATIC_MAX_LEVEL && level <= $crate::max_level() {
             $crate::private::log_minimal(
                 level,
-                &(module_path!(), file!(), line!(), $msg),
+                &($target, file!(), line!(), $msg),
             );
         }
     }};
     (
+        target: $target:expr,
         $lvl:expr,
         $msg:expr
         $(, safe: { $($safe_key:ident: $safe_value:expr),* $(,)? })?
@@ -32,72 +51,107 @@ macro_rules! log {
         $(, error: $error:expr)?
         $(,)?
     ) => {{
+        const _: () = assert!(
+            $crate::private::unique_keys(&[
+                $($(stringify!($safe_key),)*)*
+                $($(stringify!($unsafe_key),)*)*
+            ]),
+            "duplicate parameter key",
+        );
         let level = $lvl;
-        if level <= $crate::max_level() {
+        if level <= $crate::STATIC_MAX_LEVEL && level <= $crate::max_level() {
             $crate::private::log(
                 level,
-                &(module_path!(), file!(), line!(), $msg),
+                &($target, file!(), line!(), $msg),
                 &[$($((stringify!($safe_key), &$safe_value)),*)*],
                 &[$($((stringify!($unsafe_key), &$unsafe_value)),*)*],
                 None $(.or(Some(&$error)))?,
             );
         }
     }};
+    ($lvl:expr, $($v:tt)*) => {
+        $crate::log!(target: module_path!(), $lvl, $($v)*)
+    };
 }
 
 /// Logs a message at the "fatal" level.
 #[macro_export]
 macro_rules! fatal {
+    (target: $target:expr, $($v:tt)*) => {
+        $crate::log!(target: $target, $crate::Level::Fatal, $($v)*)
+    };
     ($($v:tt)*) => {
         $crate::log!($crate::Level::Fatal, $($v)*)
-    }
+    };
 }
 
 /// Logs a message at the "error" level.
 #[macro_export]
 macro_rules! error {
+    (target: $target:expr, $($v:tt)*) => {
+        $crate::log!(target: $target, $crate::Level::Error, $($v)*)
+    };
     ($($v:tt)*) => {
         $crate::log!($crate::Level::Error, $($v)*)
-    }
+    };
 }
 
 /// Logs a message at the "warn" level.
 #[macro_export]
 macro_rules! warn {
+    (target: $target:expr, $($v:tt)*) => {
+        $crate::log!(target: $target, $crate::Level::Warn, $($v)*)
+    };
     ($($v:tt)*) => {
         $crate::log!($crate::Level::Warn, $($v)*)
-    }
+    };
 }
 
 /// Logs a message at the "info" level.
 #[macro_export]
 macro_rules! info {
+    (target: $target:expr, $($v:tt)*) => {
+        $crate::log!(target: $target, $crate::Level::Info, $($v)*)
+    };
     ($($v:tt)*) => {
         $crate::log!($crate::Level::Info, $($v)*)
-    }
+    };
 }
 
 /// Logs a message at the "debug" level.
 #[macro_export]
 macro_rules! debug {
+    (target: $target:expr, $($v:tt)*) => {
+        $crate::log!(target: $target, $crate::Level::Debug, $($v)*)
+    };
     ($($v:tt)*) => {
         $crate::log!($crate::Level::Debug, $($v)*)
-    }
+    };
 }
 
 /// Logs a message at the "trace" level.
 #[macro_export]
 macro_rules! trace {
+    (target: $target:expr, $($v:tt)*) => {
+        $crate::log!(target: $target, $crate::Level::Trace, $($v)*)
+    };
     ($($v:tt)*) => {
         $crate::log!($crate::Level::Trace, $($v)*)
-    }
+    };
 }
 
 /// Determines if a message logged at the specified level in the same module would be logged or not.
+///
+/// Like the logging macros, the target can be overridden with a leading `target:` argument.
 #[macro_export]
 macro_rules! enabled {
-    ($lvl:expr) => {{
+    (target: $target:expr, $lvl:expr) => {{
         let level = $lvl;
-        level <= $crate::max_level() && $crate::private::enabled(level, module_path!())
+        level <= $crate::STATIC_MAX_LEVEL
+            && level <= $crate::max_level()
+            && $crate::private::enabled(level, $target)
     }};
+    ($lvl:expr) => {
+        $crate::enabled!(target: module_path!(), $lvl)
+    };
 }
@@ -0,0 +1,223 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Support for forwarding records from `slog` to `witchcraft-log`.
+//!
+//! Requires the `slog` Cargo feature.
+//!
+//! Unlike the `log` crate, `slog` records carry structured key-value pairs. Since `slog` has no notion of parameter
+//! safety, a `BridgedDrain` is created with a callback which decides whether each key should be forwarded as a safe
+//! or unsafe parameter. The formatted message is forwarded as the unsafe `message` parameter, as it is in the `bridge`
+//! module.
+//!
+//! Keys are required to be `&'static str`s, so this module is not compatible with `slog`'s `dynamic-keys` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use slog::{o, Logger};
+//! use witchcraft_log::slog::BridgedDrain;
+//!
+//! let drain = BridgedDrain::new(|key| key == "request_id");
+//! let logger = Logger::root(drain, o!("service" => "my-service"));
+//!
+//! slog::info!(logger, "handled request"; "request_id" => 15, "user" => "alice");
+//! ```
+
+use crate::{Level, Metadata, Record};
+use serde::{Serialize, Serializer};
+use slog::{Drain, Key, Never, OwnedKVList, KV};
+use std::fmt::{self, Write};
+
+/// A `slog::Drain` implementation that forwards records to the `witchcraft-log` logger.
+pub struct BridgedDrain<F> {
+    is_safe: F,
+}
+
+impl<F> BridgedDrain<F>
+where
+    F: Fn(&str) -> bool,
+{
+    /// Creates a new drain.
+    ///
+    /// The `is_safe` callback is invoked with the key of each key-value pair in a record, and determines if the pair
+    /// is forwarded as a safe or unsafe parameter.
+    pub fn new(is_safe: F) -> BridgedDrain<F> {
+        BridgedDrain { is_safe }
+    }
+}
+
+fn cvt_level(level: slog::Level) -> Level {
+    match level {
+        slog::Level::Critical => Level::Fatal,
+        slog::Level::Error => Level::Error,
+        slog::Level::Warning => Level::Warn,
+        slog::Level::Info => Level::Info,
+        slog::Level::Debug => Level::Debug,
+        slog::Level::Trace => Level::Trace,
+    }
+}
+
+impl<F> Drain for BridgedDrain<F>
+where
+    F: Fn(&str) -> bool,
+{
+    type Ok = ();
+    type Err = Never;
+
+    fn log(&self, record: &slog::Record<'_>, values: &OwnedKVList) -> Result<(), Never> {
+        // slog's Logger doesn't check is_enabled before logging, so skip collecting parameters for filtered records
+        let level = cvt_level(record.level());
+        if !self.is_enabled(record.level())
+            || !crate::logger().enabled(
+                &Metadata::builder()
+                    .level(level)
+                    .target(record.module())
+                    .build(),
+            )
+        {
+            return Ok(());
+        }
+
+        let mut collector = Collector { params: vec![] };
+        // errors are only returned by our serializer if formatting a value fails, in which case we just drop it
+        let _ = record.kv().serialize(record, &mut collector);
+        let _ = values.serialize(record, &mut collector);
+
+        let message = record.msg().to_string();
+        let mut safe_params = vec![];
+        let mut unsafe_params = vec![("message", &message as &dyn erased_serde::Serialize)];
+        for (key, value) in &collector.params {
+            if (self.is_safe)(key) {
+                safe_params.push((*key, value as &dyn erased_serde::Serialize));
+            } else {
+                unsafe_params.push((*key, value as &dyn erased_serde::Serialize));
+            }
+        }
+
+        crate::logger().log(
+            &Record::builder()
+                .level(level)
+                .target(record.module())
+                .file(Some(record.file()))
+                .line(Some(record.line()))
+                .safe_params(&safe_params)
+                .unsafe_params(&unsafe_params)
+                .build(),
+        );
+
+        Ok(())
+    }
+
+    fn is_enabled(&self, level: slog::Level) -> bool {
+        let level = cvt_level(level);
+        level <= crate::STATIC_MAX_LEVEL && level <= crate::max_level()
+    }
+}
+
+enum Value {
+    Str(String),
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    F64(f64),
+    Unit,
+    None,
+}
+
+impl Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Value::Str(v) => serializer.serialize_str(v),
+            Value::Bool(v) => serializer.serialize_bool(*v),
+            Value::I64(v) => serializer.serialize_i64(*v),
+            Value::U64(v) => serializer.serialize_u64(*v),
+            Value::F64(v) => serializer.serialize_f64(*v),
+            Value::Unit => serializer.serialize_unit(),
+            Value::None => serializer.serialize_none(),
+        }
+    }
+}
+
+struct Collector {
+    params: Vec<(&'static str, Value)>,
+}
+
+impl Collector {
+    fn push(&mut self, key: Key, value: Value) -> slog::Result {
+        // record values are serialized before logger values, so the most specific value of a key wins
+        if !self.params.iter().any(|(k, _)| *k == key) {
+            self.params.push((key, value));
+        }
+        Ok(())
+    }
+}
+
+macro_rules! emit {
+    ($($method:ident: $t:ty => $variant:ident,)*) => {
+        $(
+            fn $method(&mut self, key: Key, val: $t) -> slog::Result {
+                self.push(key, Value::$variant(val.into()))
+            }
+        )*
+    };
+}
+
+impl slog::Serializer for Collector {
+    fn emit_arguments(&mut self, key: Key, val: &fmt::Arguments<'_>) -> slog::Result {
+        let mut s = String::new();
+        s.write_fmt(*val)?;
+        self.push(key, Value::Str(s))
+    }
+
+    fn emit_str(&mut self, key: Key, val: &str) -> slog::Result {
+        self.push(key, Value::Str(val.to_string()))
+    }
+
+    fn emit_char(&mut self, key: Key, val: char) -> slog::Result {
+        self.push(key, Value::Str(val.to_string()))
+    }
+
+    fn emit_usize(&mut self, key: Key, val: usize) -> slog::Result {
+        self.push(key, Value::U64(val as u64))
+    }
+
+    fn emit_isize(&mut self, key: Key, val: isize) -> slog::Result {
+        self.push(key, Value::I64(val as i64))
+    }
+
+    fn emit_unit(&mut self, key: Key) -> slog::Result {
+        self.push(key, Value::Unit)
+    }
+
+    fn emit_none(&mut self, key: Key) -> slog::Result {
+        self.push(key, Value::None)
+    }
+
+    emit! {
+        emit_bool: bool => Bool,
+        emit_u8: u8 => U64,
+        emit_u16: u16 => U64,
+        emit_u32: u32 => U64,
+        emit_u64: u64 => U64,
+        emit_i8: i8 => I64,
+        emit_i16: i16 => I64,
+        emit_i32: i32 => I64,
+        emit_i64: i64 => I64,
+        emit_f32: f32 => F64,
+        emit_f64: f64 => F64,
+    }
+}
@@ -12,20 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use crate::bridge::{self, BridgedLogger};
-use crate::{Level, LevelFilter, Log, Metadata, Record};
+use crate::{Level, LevelFilter, Log, Metadata, Record, STATIC_MAX_LEVEL};
 use conjure_error::Error;
 use serde_value::Value;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
 thread_local! {
-    static RECORDS: RefCell<Vec<TestRecord>> = RefCell::new(vec![]);
+    static RECORDS: RefCell<Vec<TestRecord>> = const { RefCell::new(vec![]) };
+    // filters in the logger's enabled method without racing other tests on the global max level
+    static ENABLED_LEVEL: Cell<LevelFilter> = const { Cell::new(LevelFilter::Trace) };
 }
 
 struct TestLogger;
 
 impl Log for TestLogger {
-    fn enabled(&self, _: &Metadata<'_>) -> bool {
-        true
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= ENABLED_LEVEL.with(|l| l.get())
     }
 
     fn log(&self, record: &Record<'_>) {
@@ -67,6 +69,7 @@ struct TestRecord {
 fn init() {
     let _ = crate::set_logger(&TestLogger);
     crate::set_max_level(LevelFilter::Trace);
+    ENABLED_LEVEL.with(|l| l.set(LevelFilter::Trace));
     RECORDS.with(|r| r.borrow_mut().clear());
 }
 
@@ -93,6 +96,25 @@ fn minimal() {
     assert_eq!(records[0].error, None);
 }
 
+#[test]
+fn target() {
+    init();
+
+    assert!(enabled!(target: "foo", Level::Info));
+    info!(target: "foo", "message");
+    log!(target: "bar", Level::Warn, "message", safe: { safe_param: 1 });
+    let records = get_records();
+    assert_eq!(records.len(), 2);
+
+    assert_eq!(records[0].level, Level::Info);
+    assert_eq!(records[0].target, "foo");
+    assert_eq!(records[0].message, "message");
+
+    assert_eq!(records[1].level, Level::Warn);
+    assert_eq!(records[1].target, "bar");
+    assert_eq!(records[1].safe_params, &[("safe_param", Value::I32(1))]);
+}
+
 #[test]
 fn params() {
     init();
@@ -125,6 +147,54 @@ fn params() {
     );
 }
 
+#[test]
+fn unique_keys() {
+    assert!(crate::private::unique_keys(&[]));
+    assert!(crate::private::unique_keys(&["a", "ab", "b"]));
+    assert!(!crate::private::unique_keys(&["a", "ab", "a"]));
+}
+
+// run by CI with a `max_level_*` feature enabled as well as without
+#[test]
+fn static_max_level() {
+    init();
+
+    trace!("message");
+    debug!("message");
+    info!("message");
+    let levels = get_records().iter().map(|r| r.level).collect::<Vec<_>>();
+
+    let expected = [Level::Trace, Level::Debug, Level::Info]
+        .iter()
+        .copied()
+        .filter(|level| *level <= STATIC_MAX_LEVEL)
+        .collect::<Vec<_>>();
+    assert_eq!(levels, expected);
+    assert_eq!(enabled!(Level::Debug), Level::Debug <= STATIC_MAX_LEVEL);
+}
+
+#[test]
+fn lazy_params() {
+    init();
+
+    let calls = Cell::new(0);
+    let param = crate::lazy(|| {
+        calls.set(calls.get() + 1);
+        "foobar"
+    });
+    assert_eq!(calls.get(), 0);
+
+    info!("message", safe: { lazy_param: param });
+    assert_eq!(calls.get(), 1);
+
+    let records = get_records();
+    assert_eq!(records.len(), 1);
+    assert_eq!(
+        records[0].safe_params,
+        &[("lazy_param", Value::String("foobar".to_string()))],
+    );
+}
+
 #[test]
 fn errors() {
     init();
@@ -159,3 +229,52 @@ fn bridge() {
     );
     assert_eq!(records[0].error, None);
 }
+
+#[test]
+#[cfg(feature = "slog")]
+fn slog() {
+    use crate::slog::BridgedDrain;
+
+    init();
+
+    let logger = ::slog::Logger::root(
+        BridgedDrain::new(|key| key == "safe_param"),
+        ::slog::o!("unsafe_param" => "logger", "other" => true),
+    );
+
+    ::slog::warn!(logger, "foobar {}", 123; "safe_param" => 1, "unsafe_param" => "record");
+    let records = get_records();
+    assert_eq!(records.len(), 1);
+
+    assert_eq!(records[0].level, Level::Warn);
+    assert_eq!(records[0].target, module_path!());
+    assert_eq!(records[0].file.as_ref().unwrap(), file!());
+    assert!(records[0].line.is_some());
+    assert_eq!(records[0].message, "");
+    assert_eq!(records[0].safe_params, &[("safe_param", Value::I64(1))]);
+    assert_eq!(
+        records[0].unsafe_params,
+        &[
+            ("message", Value::String("foobar 123".to_string())),
+            ("unsafe_param", Value::String("record".to_string())),
+            ("other", Value::Bool(true)),
+        ],
+    );
+}
+
+#[test]
+#[cfg(feature = "slog")]
+fn slog_disabled() {
+    use crate::slog::BridgedDrain;
+
+    init();
+    ENABLED_LEVEL.with(|l| l.set(LevelFilter::Info));
+
+    let logger = ::slog::Logger::root(BridgedDrain::new(|_| true), ::slog::o!());
+
+    ::slog::debug!(logger, "foobar"; "param" => 1);
+    assert_eq!(get_records().len(), 0);
+
+    ::slog::info!(logger, "foobar"; "param" => 1);
+    assert_eq!(get_records().len(), 1);
+}
@@ -0,0 +1,114 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{MetricId, MetricRegistry};
+
+/// Information identifying the build of a binary.
+///
+/// It is normally created with the [`build_info!`](crate::build_info!) macro.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildInfo {
+    name: &'static str,
+    version: &'static str,
+    git_sha: Option<&'static str>,
+    build_time: Option<&'static str>,
+}
+
+impl BuildInfo {
+    /// Creates a new `BuildInfo` with the specified name and version.
+    pub fn new(name: &'static str, version: &'static str) -> BuildInfo {
+        BuildInfo {
+            name,
+            version,
+            git_sha: None,
+            build_time: None,
+        }
+    }
+
+    /// Sets the Git commit the binary was built from.
+    pub fn with_git_sha(mut self, git_sha: &'static str) -> BuildInfo {
+        self.git_sha = Some(git_sha);
+        self
+    }
+
+    /// Sets the time the binary was built.
+    pub fn with_build_time(mut self, build_time: &'static str) -> BuildInfo {
+        self.build_time = Some(build_time);
+        self
+    }
+
+    /// Returns the name of the binary.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the version of the binary.
+    pub fn version(&self) -> &'static str {
+        self.version
+    }
+
+    /// Returns the Git commit the binary was built from, if known.
+    pub fn git_sha(&self) -> Option<&'static str> {
+        self.git_sha
+    }
+
+    /// Returns the time the binary was built, if known.
+    pub fn build_time(&self) -> Option<&'static str> {
+        self.build_time
+    }
+
+    /// Registers a `build.info` gauge with a constant value of 1 in the registry.
+    ///
+    /// The gauge is tagged with `name`, `version`, and if known, `gitSha` and `buildTime`.
+    pub fn register(&self, registry: &MetricRegistry) {
+        let mut id = MetricId::new("build.info")
+            .with_tag("name", self.name)
+            .with_tag("version", self.version);
+        if let Some(git_sha) = self.git_sha {
+            id = id.with_tag("gitSha", git_sha);
+        }
+        if let Some(build_time) = self.build_time {
+            id = id.with_tag("buildTime", build_time);
+        }
+
+        registry.gauge(id, || 1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Metric;
+    use serde_value::Value;
+
+    #[test]
+    fn register() {
+        let registry = MetricRegistry::new();
+        BuildInfo::new("foo", "1.0.0")
+            .with_git_sha("abc123")
+            .register(&registry);
+
+        let metrics = registry.metrics();
+        let (id, metric) = metrics.iter().next().unwrap();
+        assert_eq!(id.name(), "build.info");
+        let tags = id.tags().iter().collect::<Vec<_>>();
+        assert_eq!(
+            tags,
+            &[("gitSha", "abc123"), ("name", "foo"), ("version", "1.0.0")]
+        );
+        match metric {
+            Metric::Gauge(gauge) => assert_eq!(gauge.value(), Value::I32(1)),
+            _ => panic!("expected a gauge"),
+        }
+    }
+}
@@ -0,0 +1,226 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{Counter, Gauge, Metered, MetricId, MetricRegistry, Timer};
+use parking_lot::Mutex;
+use serde_value::Value;
+use std::borrow::Cow;
+use std::sync::Arc;
+
+/// A cache which can be instrumented by an [`InstrumentedCache`].
+pub trait Cache: 'static + Sync + Send {
+    /// The type of the cache's keys.
+    type Key;
+
+    /// The type of the cache's values.
+    type Value;
+
+    /// Returns the value associated with the key, if present.
+    fn get(&self, key: &Self::Key) -> Option<Self::Value>;
+
+    /// Inserts a value into the cache, returning the number of entries evicted to make room for it.
+    fn insert(&self, key: Self::Key, value: Self::Value) -> u64;
+
+    /// Returns the number of entries in the cache.
+    fn size(&self) -> u64;
+}
+
+type SizeFn = Box<dyn Fn() -> Option<u64> + Sync + Send>;
+
+// Stored in the registry so that it reports the total size of all live caches with its name. Caches are held weakly
+// so they stop being reported once dropped.
+#[derive(Default)]
+struct CacheSizeGauge {
+    caches: Mutex<Vec<SizeFn>>,
+}
+
+impl Gauge for CacheSizeGauge {
+    fn value(&self) -> Value {
+        let mut total = 0;
+        self.caches.lock().retain(|size| match size() {
+            Some(size) => {
+                total += size;
+                true
+            }
+            None => false,
+        });
+        Value::U64(total)
+    }
+}
+
+/// A wrapper around a [`Cache`] which records metrics about its use.
+///
+/// It registers the following metrics, each tagged with `cache` set to the cache's name:
+///
+/// * `cache.hit` - a meter tracking lookups which found a value.
+/// * `cache.miss` - a meter tracking lookups which did not find a value.
+/// * `cache.load` - a timer tracking the duration of loads of missing values by [`InstrumentedCache::get_with`].
+/// * `cache.eviction` - a counter tracking the number of entries evicted from the cache.
+/// * `cache.size` - a gauge reporting the number of entries in the cache.
+///
+/// Caches created with the same name in the same registry share their metrics, and `cache.size` reports their total
+/// size.
+pub struct InstrumentedCache<C> {
+    cache: Arc<C>,
+    hit: Arc<dyn Metered>,
+    miss: Arc<dyn Metered>,
+    load: Arc<Timer>,
+    eviction: Arc<Counter>,
+}
+
+impl<C> InstrumentedCache<C>
+where
+    C: Cache,
+{
+    /// Wraps a cache with the specified name, registering its metrics in the registry.
+    pub fn new<T>(registry: &MetricRegistry, name: T, cache: C) -> InstrumentedCache<C>
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let name = name.into();
+        let id = |metric| MetricId::new(metric).with_tag("cache", name.clone());
+
+        let cache = Arc::new(cache);
+        let size = registry
+            .typed_gauge_with(id("cache.size"), CacheSizeGauge::default)
+            .unwrap_or_else(|| panic!("cache.size gauge already registered for cache {}", name));
+        let weak = Arc::downgrade(&cache);
+        size.caches
+            .lock()
+            .push(Box::new(move || weak.upgrade().map(|c| c.size())));
+
+        InstrumentedCache {
+            hit: registry.meter(id("cache.hit")),
+            miss: registry.meter(id("cache.miss")),
+            load: registry.timer(id("cache.load")),
+            eviction: registry.counter(id("cache.eviction")),
+            cache,
+        }
+    }
+
+    /// Returns a reference to the wrapped cache.
+    ///
+    /// Operations performed directly on the wrapped cache are not recorded.
+    #[inline]
+    pub fn cache(&self) -> &C {
+        &self.cache
+    }
+
+    /// Returns the value associated with the key, if present.
+    pub fn get(&self, key: &C::Key) -> Option<C::Value> {
+        let value = self.cache.get(key);
+        match value {
+            Some(_) => self.hit.mark(1),
+            None => self.miss.mark(1),
+        }
+        value
+    }
+
+    /// Returns the value associated with the key, using `load` to create and insert it if absent.
+    pub fn get_with<F>(&self, key: C::Key, load: F) -> C::Value
+    where
+        F: FnOnce(&C::Key) -> C::Value,
+        C::Value: Clone,
+    {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+
+        let value = {
+            let _time = self.load.time();
+            load(&key)
+        };
+        self.insert(key, value.clone());
+        value
+    }
+
+    /// Inserts a value into the cache.
+    pub fn insert(&self, key: C::Key, value: C::Value) {
+        let evicted = self.cache.insert(key, value);
+        self.eviction.inc_by(evicted);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Cache, InstrumentedCache, Metric, MetricId, MetricRegistry};
+    use parking_lot::Mutex;
+    use serde_value::Value;
+
+    // A cache holding only the most recently inserted entry.
+    struct SingleEntryCache(Mutex<Option<(u32, String)>>);
+
+    impl Cache for SingleEntryCache {
+        type Key = u32;
+        type Value = String;
+
+        fn get(&self, key: &u32) -> Option<String> {
+            match &*self.0.lock() {
+                Some((k, v)) if k == key => Some(v.clone()),
+                _ => None,
+            }
+        }
+
+        fn insert(&self, key: u32, value: String) -> u64 {
+            match self.0.lock().replace((key, value)) {
+                Some((k, _)) if k != key => 1,
+                _ => 0,
+            }
+        }
+
+        fn size(&self) -> u64 {
+            self.0.lock().is_some() as u64
+        }
+    }
+
+    #[test]
+    fn basic() {
+        let registry = MetricRegistry::new();
+        let cache = InstrumentedCache::new(&registry, "test", SingleEntryCache(Mutex::new(None)));
+
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.get_with(1, |k| k.to_string()), "1");
+        assert_eq!(cache.get_with(1, |_| unreachable!()), "1");
+        cache.insert(2, "2".to_string());
+        assert_eq!(cache.get(&2), Some("2".to_string()));
+
+        let id = |name| MetricId::new(name).with_tag("cache", "test");
+        assert_eq!(registry.meter(id("cache.hit")).count(), 2);
+        assert_eq!(registry.meter(id("cache.miss")).count(), 2);
+        assert_eq!(registry.timer(id("cache.load")).count(), 1);
+        assert_eq!(registry.counter(id("cache.eviction")).count(), 1);
+        match registry.remove(id("cache.size")).unwrap() {
+            Metric::Gauge(gauge) => assert_eq!(gauge.value(), Value::U64(1)),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn size_summed_across_caches() {
+        let registry = MetricRegistry::new();
+        let a = InstrumentedCache::new(&registry, "test", SingleEntryCache(Mutex::new(None)));
+        let b = InstrumentedCache::new(&registry, "test", SingleEntryCache(Mutex::new(None)));
+
+        a.insert(1, "1".to_string());
+        b.insert(2, "2".to_string());
+
+        let size = match registry.get(MetricId::new("cache.size").with_tag("cache", "test")) {
+            Some(Metric::Gauge(gauge)) => gauge,
+            _ => panic!("gauge not registered"),
+        };
+        assert_eq!(size.value(), Value::U64(2));
+
+        drop(a);
+        assert_eq!(size.value(), Value::U64(1));
+    }
+}
@@ -0,0 +1,182 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{Counter, Gauge, Metered, MetricId, MetricRegistry};
+use serde_value::Value;
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+
+/// The state of a circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CircuitBreakerState {
+    /// Calls are permitted.
+    Closed,
+    /// A limited number of trial calls are permitted to determine if the breaker should close.
+    HalfOpen,
+    /// Calls are rejected.
+    Open,
+}
+
+impl CircuitBreakerState {
+    fn from_u8(n: u8) -> CircuitBreakerState {
+        match n {
+            0 => CircuitBreakerState::Closed,
+            1 => CircuitBreakerState::HalfOpen,
+            _ => CircuitBreakerState::Open,
+        }
+    }
+
+    /// Returns the standard string name of the state.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CircuitBreakerState::Closed => "CLOSED",
+            CircuitBreakerState::HalfOpen => "HALF_OPEN",
+            CircuitBreakerState::Open => "OPEN",
+        }
+    }
+}
+
+impl fmt::Display for CircuitBreakerState {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.pad(self.as_str())
+    }
+}
+
+// Stored in the registry so that all metrics instances for a breaker share it.
+struct StateGauge {
+    state: AtomicU8,
+}
+
+impl Gauge for StateGauge {
+    fn value(&self) -> Value {
+        let state = CircuitBreakerState::from_u8(self.state.load(Ordering::Relaxed));
+        Value::String(state.as_str().to_string())
+    }
+}
+
+/// A helper which records metrics about a circuit breaker using a standard naming scheme.
+///
+/// It registers the following metrics, each tagged with `circuitBreaker` set to the breaker's name:
+///
+/// * `circuitBreaker.state` - a gauge reporting the name of the breaker's current [`CircuitBreakerState`].
+/// * `circuitBreaker.trip` - a meter tracking transitions of the breaker into the open state.
+/// * `circuitBreaker.rejected` - a counter tracking the number of calls rejected by the breaker.
+///
+/// The breaker starts out closed. Metrics created for the same breaker in the same registry share their state.
+pub struct CircuitBreakerMetrics {
+    state: Arc<StateGauge>,
+    trip: Arc<dyn Metered>,
+    rejected: Arc<Counter>,
+}
+
+impl CircuitBreakerMetrics {
+    /// Creates metrics for the circuit breaker with the specified name, registering them in the registry.
+    pub fn new<T>(registry: &MetricRegistry, name: T) -> CircuitBreakerMetrics
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let name = name.into();
+        let id = |metric| MetricId::new(metric).with_tag("circuitBreaker", name.clone());
+
+        let state = registry
+            .typed_gauge_with(id("circuitBreaker.state"), || StateGauge {
+                state: AtomicU8::new(CircuitBreakerState::Closed as u8),
+            })
+            .unwrap_or_else(|| {
+                panic!(
+                    "circuitBreaker.state gauge already registered for breaker {}",
+                    name
+                )
+            });
+
+        CircuitBreakerMetrics {
+            state,
+            trip: registry.meter(id("circuitBreaker.trip")),
+            rejected: registry.counter(id("circuitBreaker.rejected")),
+        }
+    }
+
+    /// Returns the breaker's current state.
+    #[inline]
+    pub fn state(&self) -> CircuitBreakerState {
+        CircuitBreakerState::from_u8(self.state.state.load(Ordering::Relaxed))
+    }
+
+    /// Records a transition of the breaker to a new state.
+    ///
+    /// A transition into the open state from another state is recorded as a trip.
+    pub fn set_state(&self, state: CircuitBreakerState) {
+        let old =
+            CircuitBreakerState::from_u8(self.state.state.swap(state as u8, Ordering::Relaxed));
+        if state == CircuitBreakerState::Open && old != CircuitBreakerState::Open {
+            self.trip.mark(1);
+        }
+    }
+
+    /// Records a call rejected by the breaker.
+    #[inline]
+    pub fn reject(&self) {
+        self.rejected.inc();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{CircuitBreakerMetrics, CircuitBreakerState, Metric, MetricId, MetricRegistry};
+    use serde_value::Value;
+
+    #[test]
+    fn basic() {
+        let registry = MetricRegistry::new();
+        let metrics = CircuitBreakerMetrics::new(&registry, "test");
+
+        let id = |name| MetricId::new(name).with_tag("circuitBreaker", "test");
+        let state = match registry.remove(id("circuitBreaker.state")).unwrap() {
+            Metric::Gauge(gauge) => gauge,
+            _ => panic!(),
+        };
+        assert_eq!(metrics.state(), CircuitBreakerState::Closed);
+        assert_eq!(state.value(), Value::String("CLOSED".to_string()));
+
+        metrics.set_state(CircuitBreakerState::Open);
+        metrics.set_state(CircuitBreakerState::Open);
+        metrics.reject();
+        metrics.set_state(CircuitBreakerState::HalfOpen);
+        assert_eq!(state.value(), Value::String("HALF_OPEN".to_string()));
+        metrics.set_state(CircuitBreakerState::Open);
+        metrics.reject();
+
+        assert_eq!(metrics.state(), CircuitBreakerState::Open);
+        assert_eq!(state.value(), Value::String("OPEN".to_string()));
+        assert_eq!(registry.meter(id("circuitBreaker.trip")).count(), 2);
+        assert_eq!(registry.counter(id("circuitBreaker.rejected")).count(), 2);
+    }
+
+    #[test]
+    fn state_shared_between_metrics() {
+        let registry = MetricRegistry::new();
+        let a = CircuitBreakerMetrics::new(&registry, "test");
+        let b = CircuitBreakerMetrics::new(&registry, "test");
+
+        b.set_state(CircuitBreakerState::Open);
+        assert_eq!(a.state(), CircuitBreakerState::Open);
+
+        // the breaker is already open, so this isn't another trip
+        a.set_state(CircuitBreakerState::Open);
+        let id = MetricId::new("circuitBreaker.trip").with_tag("circuitBreaker", "test");
+        assert_eq!(registry.meter(id).count(), 1);
+    }
+}
@@ -12,8 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 pub(crate) static SYSTEM_CLOCK: Lazy<Arc<SystemClock>> = Lazy::new(|| Arc::new(SystemClock));
 
@@ -33,32 +34,56 @@ impl Clock for SystemClock {
     }
 }
 
-#[cfg(test)]
-pub mod test {
-    use super::*;
-    use parking_lot::Mutex;
-    use std::time::Duration;
+/// A `Clock` implementation which only advances when told to.
+///
+/// This is intended for use in tests. Installing it in a registry with [`MetricRegistry::set_clock`] makes the
+/// registry's meters, timers, and helpers use it, so tests can simulate hours of behavior such as rate decay
+/// instantly.
+///
+/// [`MetricRegistry::set_clock`]: crate::MetricRegistry::set_clock
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use witchcraft_metrics::{ManualClock, MetricRegistry};
+///
+/// let clock = Arc::new(ManualClock::new());
+/// let mut registry = MetricRegistry::new();
+/// registry.set_clock(clock.clone());
+///
+/// let meter = registry.meter("requests");
+/// meter.mark(60);
+/// clock.advance(Duration::from_secs(60));
+/// assert!(meter.one_minute_rate() > 0.);
+/// ```
+pub struct ManualClock {
+    now: Mutex<Instant>,
+}
 
-    pub struct TestClock {
-        now: Mutex<Instant>,
+impl Default for ManualClock {
+    fn default() -> ManualClock {
+        ManualClock::new()
     }
+}
 
-    impl TestClock {
-        #[allow(clippy::new_without_default)]
-        pub fn new() -> TestClock {
-            TestClock {
-                now: Mutex::new(Instant::now()),
-            }
+impl ManualClock {
+    /// Creates a new clock starting at the current system time.
+    pub fn new() -> ManualClock {
+        ManualClock {
+            now: Mutex::new(Instant::now()),
         }
+    }
 
-        pub fn advance(&self, dur: Duration) {
-            *self.now.lock() += dur;
-        }
+    /// Advances the clock by the specified duration.
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock() += duration;
     }
+}
 
-    impl Clock for TestClock {
-        fn now(&self) -> Instant {
-            *self.now.lock()
-        }
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.now.lock()
     }
 }
@@ -12,11 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::sync::atomic::{AtomicI64, Ordering};
+use crate::gauge::{f64_value, fetch_add_f64};
+use crate::Gauge;
+use serde_value::Value;
+use std::convert::TryFrom;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
 
 /// A metric which counts a value.
 #[derive(Debug, Default)]
-pub struct Counter(AtomicI64);
+pub struct Counter {
+    value: AtomicI64,
+    non_negative: bool,
+}
 
 impl Counter {
     /// Creates a new counter initialized to 0.
@@ -25,10 +32,23 @@ impl Counter {
         Counter::default()
     }
 
+    /// Creates a new counter initialized to 0 which is expected to never go negative.
+    ///
+    /// The counter behaves identically to one created by [`Counter::new`], but logs a warning via `witchcraft-log` when
+    /// an update takes its value below zero. This usually indicates an accounting bug, such as a decrement without a
+    /// matching increment.
+    #[inline]
+    pub fn new_non_negative() -> Counter {
+        Counter {
+            value: AtomicI64::new(0),
+            non_negative: true,
+        }
+    }
+
     /// Resets the counter to 0.
     #[inline]
     pub fn clear(&self) {
-        self.0.store(0, Ordering::Relaxed);
+        self.value.store(0, Ordering::Relaxed);
     }
 
     /// Adds 1 to the counter.
@@ -43,28 +63,155 @@ impl Counter {
         self.sub(1);
     }
 
+    /// Adds an unsigned number to the counter.
+    ///
+    /// Values larger than `i64::MAX` are clamped to `i64::MAX`.
+    #[inline]
+    pub fn inc_by(&self, n: u64) {
+        self.add(i64::try_from(n).unwrap_or(i64::MAX));
+    }
+
+    /// Subtracts an unsigned number from the counter.
+    ///
+    /// Values larger than `i64::MAX` are clamped to `i64::MAX`.
+    #[inline]
+    pub fn dec_by(&self, n: u64) {
+        self.sub(i64::try_from(n).unwrap_or(i64::MAX));
+    }
+
     /// Adds a number to the counter.
     #[inline]
     pub fn add(&self, n: i64) {
-        self.0.fetch_add(n, Ordering::Relaxed);
+        let old = self.value.fetch_add(n, Ordering::Relaxed);
+        self.check_non_negative(old, old.wrapping_add(n));
     }
 
     /// Subtracts a number from the counter.
     #[inline]
     pub fn sub(&self, n: i64) {
-        self.0.fetch_sub(n, Ordering::Relaxed);
+        let old = self.value.fetch_sub(n, Ordering::Relaxed);
+        self.check_non_negative(old, old.wrapping_sub(n));
     }
 
     /// Returns the current value of the counter.
     #[inline]
     pub fn count(&self) -> i64 {
-        self.0.load(Ordering::Relaxed)
+        self.value.load(Ordering::Relaxed)
+    }
+
+    /// Raises the counter to at least the specified value.
+    #[cfg(feature = "metrics")]
+    #[inline]
+    pub(crate) fn raise_to(&self, n: i64) {
+        self.value.fetch_max(n, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn check_non_negative(&self, old: i64, new: i64) {
+        // only log on the transition to avoid spamming while the counter stays negative
+        if self.non_negative && old >= 0 && new < 0 {
+            witchcraft_log::warn!(
+                "non-negative counter went below zero",
+                safe: { previous: old, count: new },
+            );
+        }
+    }
+}
+
+/// A metric which counts a floating point value, such as an amount of money or time.
+///
+/// It is updated without locking, and is registered with a [`MetricRegistry`](crate::MetricRegistry) as a gauge
+/// reporting its count. The reported value is `None` when the count is not finite, since many metrics formats can't
+/// represent NaN or infinite values.
+#[derive(Debug, Default)]
+pub struct F64Counter {
+    bits: AtomicU64,
+}
+
+impl F64Counter {
+    /// Creates a new counter initialized to 0.
+    #[inline]
+    pub fn new() -> F64Counter {
+        F64Counter::default()
+    }
+
+    /// Resets the counter to 0.
+    #[inline]
+    pub fn clear(&self) {
+        self.bits.store(0f64.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Adds a number to the counter.
+    #[inline]
+    pub fn add(&self, n: f64) {
+        fetch_add_f64(&self.bits, n);
+    }
+
+    /// Subtracts a number from the counter.
+    #[inline]
+    pub fn sub(&self, n: f64) {
+        fetch_add_f64(&self.bits, -n);
+    }
+
+    /// Returns the current value of the counter.
+    #[inline]
+    pub fn count(&self) -> f64 {
+        f64::from_bits(self.bits.load(Ordering::Relaxed))
+    }
+}
+
+impl Gauge for F64Counter {
+    fn value(&self) -> Value {
+        f64_value(self.count())
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::Counter;
+    use crate::{Counter, F64Counter, Gauge};
+    use serde_value::Value;
+    use std::cell::RefCell;
+    use witchcraft_log::{Level, LevelFilter, Log, Metadata, Record};
+
+    thread_local! {
+        static RECORDS: RefCell<Vec<TestRecord>> = const { RefCell::new(vec![]) };
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct TestRecord {
+        level: Level,
+        message: &'static str,
+        safe_params: Vec<(&'static str, Value)>,
+    }
+
+    struct TestLogger;
+
+    impl Log for TestLogger {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record<'_>) {
+            let record = TestRecord {
+                level: record.level(),
+                message: record.message(),
+                safe_params: record
+                    .safe_params()
+                    .iter()
+                    .map(|(k, v)| (*k, serde_value::to_value(v).unwrap()))
+                    .collect(),
+            };
+            RECORDS.with(|r| r.borrow_mut().push(record));
+        }
+
+        fn flush(&self) {}
+    }
+
+    fn init_logger() {
+        let _ = witchcraft_log::set_logger(&TestLogger);
+        witchcraft_log::set_max_level(LevelFilter::Trace);
+        RECORDS.with(|r| r.borrow_mut().clear());
+    }
 
     #[test]
     fn basic() {
@@ -85,5 +232,62 @@ mod test {
 
         counter.clear();
         assert_eq!(counter.count(), 0);
+
+        counter.inc_by(5);
+        assert_eq!(counter.count(), 5);
+
+        counter.dec_by(2);
+        assert_eq!(counter.count(), 3);
+    }
+
+    #[test]
+    fn non_negative() {
+        init_logger();
+        let counter = Counter::new_non_negative();
+
+        counter.inc();
+        counter.dec_by(3);
+        assert_eq!(counter.count(), -2);
+
+        counter.dec();
+        assert_eq!(counter.count(), -3);
+
+        let records = RECORDS.with(|r| r.replace(vec![]));
+        assert_eq!(
+            records,
+            [TestRecord {
+                level: Level::Warn,
+                message: "non-negative counter went below zero",
+                safe_params: vec![("previous", Value::I64(1)), ("count", Value::I64(-2))],
+            }],
+        );
+
+        counter.add(5);
+        counter.dec_by(3);
+        assert_eq!(counter.count(), -1);
+        assert_eq!(RECORDS.with(|r| r.borrow().len()), 1);
+
+        let counter = Counter::new();
+        counter.dec();
+        assert_eq!(RECORDS.with(|r| r.borrow().len()), 1);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn f64_basic() {
+        let counter = F64Counter::new();
+        assert_eq!(counter.count(), 0.);
+
+        counter.add(1.5);
+        counter.sub(0.25);
+        assert_eq!(counter.count(), 1.25);
+        assert_eq!(counter.value(), Value::F64(1.25));
+
+        counter.add(f64::NAN);
+        assert!(counter.count().is_nan());
+        assert_eq!(counter.value(), Value::Option(None));
+
+        counter.clear();
+        assert_eq!(counter.count(), 0.);
     }
 }
@@ -0,0 +1,104 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{Clock, Histogram, Metered, MetricId, MetricRegistry};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// A helper which instruments how close operations finish to their deadlines.
+///
+/// It registers the following metrics with the tags of its ID:
+///
+/// * `<name>.remaining` - a histogram of the time remaining before the deadline when operations completed in time,
+///   in nanoseconds.
+/// * `<name>.timeout` - a meter tracking operations which completed after their deadline.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, Instant};
+/// use witchcraft_metrics::{DeadlineTimer, MetricRegistry};
+///
+/// let registry = MetricRegistry::new();
+/// let timer = DeadlineTimer::new(&registry, "request");
+///
+/// let deadline = Instant::now() + Duration::from_secs(5);
+/// // handle the request
+/// timer.complete(deadline);
+/// ```
+pub struct DeadlineTimer {
+    clock: Arc<dyn Clock>,
+    remaining: Arc<Histogram>,
+    timeout: Arc<dyn Metered>,
+}
+
+impl DeadlineTimer {
+    /// Creates a new deadline timer with the specified ID, registering its metrics in the registry.
+    ///
+    /// The timer uses the registry's clock as its time source.
+    pub fn new<T>(registry: &MetricRegistry, id: T) -> DeadlineTimer
+    where
+        T: Into<MetricId>,
+    {
+        let id = id.into();
+
+        DeadlineTimer {
+            clock: registry.clock().clone(),
+            remaining: registry.histogram(id.with_name_suffix("remaining")),
+            timeout: registry.meter(id.with_name_suffix("timeout")),
+        }
+    }
+
+    /// Records the completion of an operation with the specified deadline at the current time.
+    ///
+    /// Returns `true` if the operation completed in time.
+    pub fn complete(&self, deadline: Instant) -> bool {
+        match deadline.checked_duration_since(self.clock.now()) {
+            Some(remaining) => {
+                self.remaining.update(remaining.as_nanos() as i64);
+                true
+            }
+            None => {
+                self.timeout.mark(1);
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Clock, DeadlineTimer, ManualClock, MetricRegistry};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn basic() {
+        let clock = Arc::new(ManualClock::new());
+        let mut registry = MetricRegistry::new();
+        registry.set_clock(clock.clone());
+
+        let timer = DeadlineTimer::new(&registry, "request");
+
+        let deadline = clock.now() + Duration::from_secs(5);
+        clock.advance(Duration::from_secs(2));
+        assert!(timer.complete(deadline));
+        clock.advance(Duration::from_secs(4));
+        assert!(!timer.complete(deadline));
+
+        let remaining = registry.histogram("request.remaining");
+        assert_eq!(remaining.count(), 1);
+        assert_eq!(remaining.snapshot().max(), 3_000_000_000);
+        assert_eq!(registry.meter("request.timeout").count(), 1);
+    }
+}
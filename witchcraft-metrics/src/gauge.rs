@@ -14,6 +14,7 @@
 use serde::Serialize;
 use serde_value::Value;
 use std::any::TypeId;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 mod private {
@@ -79,6 +80,68 @@ where
     }
 }
 
+/// A gauge storing an `f64` which can be updated without locking.
+///
+/// Like a [`Counter`](crate::Counter), but for floating point values which are set directly rather than computed on
+/// read. The gauge's value is 0 when it is created. Its reported value is `None` when it is not finite, since many
+/// metrics formats can't represent NaN or infinite values.
+#[derive(Debug, Default)]
+pub struct F64Gauge {
+    bits: AtomicU64,
+}
+
+impl F64Gauge {
+    /// Creates a new gauge initialized to 0.
+    #[inline]
+    pub fn new() -> F64Gauge {
+        F64Gauge::default()
+    }
+
+    /// Sets the value of the gauge.
+    #[inline]
+    pub fn set(&self, value: f64) {
+        self.bits.store(value.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Adds a number to the value of the gauge.
+    #[inline]
+    pub fn add(&self, n: f64) {
+        fetch_add_f64(&self.bits, n);
+    }
+
+    /// Subtracts a number from the value of the gauge.
+    #[inline]
+    pub fn sub(&self, n: f64) {
+        fetch_add_f64(&self.bits, -n);
+    }
+
+    /// Returns the value of the gauge.
+    #[inline]
+    pub fn get(&self) -> f64 {
+        f64::from_bits(self.bits.load(Ordering::Relaxed))
+    }
+}
+
+impl Gauge for F64Gauge {
+    fn value(&self) -> Value {
+        f64_value(self.get())
+    }
+}
+
+pub(crate) fn fetch_add_f64(bits: &AtomicU64, n: f64) {
+    let _ = bits.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |old| {
+        Some((f64::from_bits(old) + n).to_bits())
+    });
+}
+
+pub(crate) fn f64_value(value: f64) -> Value {
+    if value.is_finite() {
+        Value::F64(value)
+    } else {
+        Value::Option(None)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -0,0 +1,103 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::MetricRegistry;
+use once_cell::sync::{Lazy, OnceCell};
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+static GLOBAL: OnceCell<Arc<MetricRegistry>> = OnceCell::new();
+
+static FALLBACK: Lazy<MetricRegistry> = Lazy::new(MetricRegistry::new);
+
+/// Sets the global registry.
+///
+/// This is intended to be called once at startup by the application, and allows library code which can't be passed a
+/// registry to register its metrics. The global registry can only be set once. Further calls will return an error.
+pub fn set_global_registry(registry: Arc<MetricRegistry>) -> Result<(), SetGlobalRegistryError> {
+    GLOBAL.set(registry).map_err(|_| SetGlobalRegistryError(()))
+}
+
+/// Returns the global registry.
+///
+/// If one has not been set, a fallback registry which is never reported is returned, so metrics registered before
+/// the global registry is set are discarded.
+pub fn global() -> &'static MetricRegistry {
+    match GLOBAL.get() {
+        Some(registry) => registry,
+        None => &FALLBACK,
+    }
+}
+
+/// Returns `true` if the global registry has been set.
+pub fn global_registry_set() -> bool {
+    GLOBAL.get().is_some()
+}
+
+/// An error trying to set the global registry when one is already set.
+#[derive(Debug)]
+pub struct SetGlobalRegistryError(());
+
+impl fmt::Display for SetGlobalRegistryError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("a global registry is already set")
+    }
+}
+
+impl Error for SetGlobalRegistryError {}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    use crate::{counter, histogram, meter, timer, MetricId};
+    use std::time::Duration;
+
+    pub(crate) fn init() -> &'static MetricRegistry {
+        let _ = set_global_registry(Arc::new(MetricRegistry::new()));
+        global()
+    }
+
+    #[test]
+    fn set_once() {
+        let registry = init();
+        assert!(global_registry_set());
+        assert!(set_global_registry(Arc::new(MetricRegistry::new())).is_err());
+        assert!(std::ptr::eq(registry, global()));
+
+        global().counter("counter").inc();
+        assert_eq!(registry.counter("counter").count(), 1);
+    }
+
+    #[test]
+    fn macros() {
+        let registry = init();
+
+        for _ in 0..2 {
+            counter!("macros.counter", "tag" => "value").inc();
+        }
+        let value = String::from("value");
+        counter!("macros.counter", "tag" => value).inc();
+        let id = MetricId::new("macros.counter").with_tag("tag", "value");
+        assert_eq!(registry.counter(id).count(), 3);
+
+        meter!("macros.meter").mark(2);
+        assert_eq!(registry.meter("macros.meter").count(), 2);
+
+        histogram!("macros.histogram",).update(1);
+        assert_eq!(registry.histogram("macros.histogram").count(), 1);
+
+        timer!("macros.timer").update(Duration::from_secs(1));
+        assert_eq!(registry.timer("macros.timer").count(), 1);
+    }
+}
@@ -49,6 +49,17 @@ impl Histogram {
         self.reservoir.update(value);
     }
 
+    /// Adds `n` occurrences of a value to the histogram.
+    ///
+    /// This is equivalent to calling [`Histogram::update`] `n` times. Its cost depends on the reservoir: it is constant
+    /// for a [`BucketReservoir`](crate::BucketReservoir), but still O(n) for an
+    /// [`ExponentiallyDecayingReservoir`](crate::ExponentiallyDecayingReservoir).
+    #[inline]
+    pub fn update_n(&self, value: i64, n: u64) {
+        self.count.fetch_add(n, Ordering::Relaxed);
+        self.reservoir.update_n(value, n);
+    }
+
     /// Returns the number of values added to the histogram.
     #[inline]
     pub fn count(&self) -> u64 {
@@ -115,5 +126,9 @@ mod test {
         histogram.update(10);
         assert_eq!(histogram.count(), 2);
         assert_eq!(histogram.snapshot().min(), 10);
+
+        histogram.update_n(5, 3);
+        assert_eq!(histogram.count(), 5);
+        assert_eq!(histogram.snapshot().min(), 5);
     }
 }
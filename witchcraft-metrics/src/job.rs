@@ -0,0 +1,176 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{Gauge, Metered, MetricId, MetricRegistry, Timer};
+use serde_value::Value;
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Stored in the registry so that all monitors of a job share it.
+#[derive(Default)]
+struct LastSuccessGauge {
+    millis: AtomicU64,
+}
+
+impl Gauge for LastSuccessGauge {
+    fn value(&self) -> Value {
+        match self.millis.load(Ordering::Relaxed) {
+            0 => Value::Option(None),
+            millis => Value::Option(Some(Box::new(Value::U64(millis)))),
+        }
+    }
+}
+
+/// A helper which instruments runs of a periodic job.
+///
+/// It registers the following metrics, each tagged with `job` set to the job's name:
+///
+/// * `job.duration` - a timer tracking the duration of runs of the job.
+/// * `job.success` - a meter tracking successful runs of the job.
+/// * `job.failure` - a meter tracking failed runs of the job.
+/// * `job.lastSuccess` - a gauge reporting the time of the last successful run of the job in milliseconds since the
+///   Unix epoch, or `None` if it has never succeeded.
+///
+/// Failed runs are additionally logged via `witchcraft-log`. Monitors created for the same job in the same registry
+/// share their metrics.
+///
+/// # Examples
+///
+/// ```
+/// use witchcraft_metrics::{JobMonitor, MetricRegistry};
+///
+/// let registry = MetricRegistry::new();
+/// let monitor = JobMonitor::new(&registry, "cleanup");
+///
+/// let result = monitor.run(|| {
+///     // do some work
+///     Ok::<_, std::io::Error>(())
+/// });
+/// ```
+pub struct JobMonitor {
+    job: Cow<'static, str>,
+    duration: Arc<Timer>,
+    success: Arc<dyn Metered>,
+    failure: Arc<dyn Metered>,
+    last_success: Arc<LastSuccessGauge>,
+}
+
+impl JobMonitor {
+    /// Creates a new monitor for the job with the specified name, registering its metrics in the registry.
+    pub fn new<T>(registry: &MetricRegistry, job: T) -> JobMonitor
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let job = job.into();
+        let id = |name| MetricId::new(name).with_tag("job", job.clone());
+
+        let last_success = registry
+            .typed_gauge_with(id("job.lastSuccess"), LastSuccessGauge::default)
+            .unwrap_or_else(|| panic!("job.lastSuccess gauge already registered for job {}", job));
+
+        JobMonitor {
+            duration: registry.timer(id("job.duration")),
+            success: registry.meter(id("job.success")),
+            failure: registry.meter(id("job.failure")),
+            last_success,
+            job,
+        }
+    }
+
+    /// Runs the job, recording its outcome.
+    ///
+    /// The job fails if it returns an `Err`, in which case the error is logged as an unsafe parameter.
+    pub fn run<F, T, E>(&self, job: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+        E: fmt::Display,
+    {
+        let result = {
+            let _time = self.duration.time();
+            job()
+        };
+
+        match &result {
+            Ok(_) => {
+                self.success.mark(1);
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |d| d.as_millis() as u64);
+                self.last_success.millis.fetch_max(now, Ordering::Relaxed);
+            }
+            Err(e) => {
+                self.failure.mark(1);
+                witchcraft_log::warn!(
+                    "job failed",
+                    safe: { job: self.job },
+                    unsafe: { error: e.to_string() },
+                );
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Gauge, JobMonitor, Metric, MetricId, MetricRegistry};
+    use serde_value::Value;
+
+    #[test]
+    fn basic() {
+        let registry = MetricRegistry::new();
+        let monitor = JobMonitor::new(&registry, "test");
+
+        let last_success = match registry
+            .remove(MetricId::new("job.lastSuccess").with_tag("job", "test"))
+            .unwrap()
+        {
+            Metric::Gauge(gauge) => gauge,
+            _ => panic!(),
+        };
+        assert_eq!(last_success.value(), Value::Option(None));
+
+        assert_eq!(monitor.run(|| Ok::<_, String>(1)), Ok(1));
+        assert_eq!(
+            monitor.run(|| Err::<i32, _>("bad".to_string())),
+            Err("bad".to_string())
+        );
+
+        let id = |name| MetricId::new(name).with_tag("job", "test");
+        assert_eq!(registry.timer(id("job.duration")).count(), 2);
+        assert_eq!(registry.meter(id("job.success")).count(), 1);
+        assert_eq!(registry.meter(id("job.failure")).count(), 1);
+        match last_success.value() {
+            Value::Option(Some(v)) => assert!(matches!(*v, Value::U64(millis) if millis > 0)),
+            v => panic!("unexpected value {:?}", v),
+        }
+    }
+
+    #[test]
+    fn last_success_shared_between_monitors() {
+        let registry = MetricRegistry::new();
+        let a = JobMonitor::new(&registry, "test");
+        let b = JobMonitor::new(&registry, "test");
+
+        assert_eq!(b.run(|| Ok::<_, String>(1)), Ok(1));
+
+        match a.last_success.value() {
+            Value::Option(Some(v)) => assert!(matches!(*v, Value::U64(millis) if millis > 0)),
+            v => panic!("unexpected value {:?}", v),
+        }
+    }
+}
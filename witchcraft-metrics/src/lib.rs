@@ -56,22 +56,48 @@
 #![doc(html_root_url = "https://docs.rs/witchcraft-metrics/0.2")]
 #![warn(missing_docs)]
 
+pub use crate::build_info::*;
+pub use crate::cache::*;
+pub use crate::circuit_breaker::*;
 pub use crate::clock::*;
 pub use crate::counter::*;
+pub use crate::deadline_timer::*;
 pub use crate::gauge::*;
+pub use crate::global::*;
 pub use crate::histogram::*;
+pub use crate::job::*;
+pub use crate::logger::*;
+pub use crate::long_task_timer::*;
 pub use crate::meter::*;
 pub use crate::metric_id::*;
+pub use crate::metric_name::*;
 pub use crate::registry::*;
 pub use crate::reservoir::*;
 pub use crate::timer::*;
 
+mod build_info;
+mod cache;
+mod circuit_breaker;
 mod clock;
 mod counter;
+mod deadline_timer;
 mod gauge;
+mod global;
 mod histogram;
+mod job;
+mod logger;
+mod long_task_timer;
+#[macro_use]
+mod macros;
 mod meter;
 mod metric_id;
+mod metric_name;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "opentelemetry")]
+pub mod opentelemetry;
+#[doc(hidden)]
+pub mod private;
 mod registry;
 mod reservoir;
 mod timer;
@@ -0,0 +1,361 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{Metered, MetricId, MetricRegistry};
+use serde::Serialize;
+use serde_value::Value;
+use std::borrow::Cow;
+use std::sync::Arc;
+use witchcraft_log::{Level, LevelFilter, Log, Metadata, Record};
+
+const LEVELS: [Level; 6] = [
+    Level::Fatal,
+    Level::Error,
+    Level::Warn,
+    Level::Info,
+    Level::Debug,
+    Level::Trace,
+];
+
+/// A wrapper around a `witchcraft_log::Log` which records metrics about the records it logs.
+///
+/// It registers the following metrics:
+///
+/// * `logging.sls` - a meter tracking the records emitted by the wrapped logger, tagged with `type` set to `service.1`
+///   and `level` set to the record's level. Records the wrapped logger isn't enabled for are not counted.
+/// * `logging.level` - a gauge reporting the global max log level, as returned by `witchcraft_log::max_level`.
+///
+/// Additional meters can be marked for records matching a [`LogRule`], which allows alerting on specific log events.
+/// Rules are only evaluated for records the wrapped logger is enabled for.
+///
+/// # Examples
+///
+/// ```
+/// use witchcraft_metrics::{InstrumentedLogger, MetricRegistry};
+/// # struct MyWitchcraftLogger;
+/// # impl witchcraft_log::Log for MyWitchcraftLogger {
+/// #    fn enabled(&self, _: &witchcraft_log::Metadata<'_>) -> bool { false }
+/// #    fn log(&self, _: &witchcraft_log::Record<'_>) {}
+/// #    fn flush(&self) {}
+/// # }
+///
+/// let registry = MetricRegistry::new();
+/// let logger = InstrumentedLogger::new(&registry, MyWitchcraftLogger);
+/// let _ = witchcraft_log::set_logger(Box::leak(Box::new(logger)));
+/// ```
+pub struct InstrumentedLogger<L> {
+    logger: L,
+    records: [Arc<dyn Metered>; 6],
+    rules: Vec<LogRule>,
+}
+
+impl<L> InstrumentedLogger<L>
+where
+    L: Log,
+{
+    /// Wraps a logger, registering its metrics in the registry.
+    pub fn new(registry: &MetricRegistry, logger: L) -> InstrumentedLogger<L> {
+        registry.gauge("logging.level", witchcraft_log::max_level);
+
+        let records = LEVELS.map(|level| {
+            registry.meter(
+                MetricId::new("logging.sls")
+                    .with_tag("type", "service.1")
+                    .with_tag("level", level.as_str()),
+            )
+        });
+
+        InstrumentedLogger {
+            logger,
+            records,
+            rules: vec![],
+        }
+    }
+
+    /// Adds a rule which marks its meter for each record matching it.
+    pub fn add_rule(&mut self, rule: LogRule) {
+        self.rules.push(rule);
+    }
+
+    /// Returns a reference to the wrapped logger.
+    pub fn logger(&self) -> &L {
+        &self.logger
+    }
+}
+
+impl<L> Log for InstrumentedLogger<L>
+where
+    L: Log,
+{
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.logger.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        // the wrapped logger may filter records itself, so only count the ones it will emit
+        if self.logger.enabled(record.metadata()) {
+            self.records[record.level() as usize - 1].mark(1);
+            for rule in &self.rules {
+                if rule.matches(record) {
+                    rule.meter.mark(1);
+                }
+            }
+        }
+        self.logger.log(record);
+    }
+
+    fn flush(&self) {
+        self.logger.flush();
+    }
+}
+
+/// A rule marking a meter for log records matching a set of predicates.
+///
+/// A record matches a rule only if it matches all of the rule's predicates. A rule with no predicates matches every
+/// record.
+///
+/// # Examples
+///
+/// ```
+/// use witchcraft_log::LevelFilter;
+/// use witchcraft_metrics::{LogRule, MetricRegistry};
+///
+/// let registry = MetricRegistry::new();
+/// let mut rule = LogRule::new(&registry, "logging.rule.connectionFailed");
+/// rule.set_max_level(LevelFilter::Warn)
+///     .set_message_contains("connection failed")
+///     .add_param("host", "db.example.com");
+/// ```
+pub struct LogRule {
+    meter: Arc<dyn Metered>,
+    max_level: LevelFilter,
+    message: Option<Cow<'static, str>>,
+    params: Vec<(Cow<'static, str>, Value)>,
+}
+
+impl LogRule {
+    /// Creates a new rule which marks the meter with the specified ID in the registry.
+    pub fn new<T>(registry: &MetricRegistry, id: T) -> LogRule
+    where
+        T: Into<MetricId>,
+    {
+        LogRule {
+            meter: registry.meter(id),
+            max_level: LevelFilter::Trace,
+            message: None,
+            params: vec![],
+        }
+    }
+
+    /// Only matches records at or above the severity of the specified level.
+    ///
+    /// Defaults to `LevelFilter::Trace`.
+    pub fn set_max_level(&mut self, max_level: LevelFilter) -> &mut LogRule {
+        self.max_level = max_level;
+        self
+    }
+
+    /// Only matches records with messages containing the specified string.
+    pub fn set_message_contains<T>(&mut self, message: T) -> &mut LogRule
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Only matches records with a safe or unsafe parameter with the specified key and value.
+    ///
+    /// Parameters are compared by their serialized representation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value fails to serialize.
+    pub fn add_param<K, V>(&mut self, key: K, value: V) -> &mut LogRule
+    where
+        K: Into<Cow<'static, str>>,
+        V: Serialize,
+    {
+        let value = serde_value::to_value(value).expect("value failed to serialize");
+        self.params.push((key.into(), value));
+        self
+    }
+
+    fn matches(&self, record: &Record<'_>) -> bool {
+        if record.level() > self.max_level {
+            return false;
+        }
+
+        if let Some(message) = &self.message {
+            if !record.message().contains(&**message) {
+                return false;
+            }
+        }
+
+        // only serialize parameters with matching keys, since they may be expensive to compute
+        self.params.iter().all(|(key, value)| {
+            record
+                .safe_params()
+                .iter()
+                .chain(record.unsafe_params())
+                .filter(|(k, _)| *k == key)
+                .any(|(_, v)| serde_value::to_value(v).ok().as_ref() == Some(value))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Metric;
+
+    struct NopLogger(LevelFilter);
+
+    impl Log for NopLogger {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            metadata.level() <= self.0
+        }
+
+        fn log(&self, _: &Record<'_>) {}
+
+        fn flush(&self) {}
+    }
+
+    #[test]
+    fn basic() {
+        let registry = MetricRegistry::new();
+        let logger = InstrumentedLogger::new(&registry, NopLogger(LevelFilter::Trace));
+
+        logger.log(&Record::builder().level(Level::Warn).build());
+        logger.log(&Record::builder().level(Level::Warn).build());
+        logger.log(&Record::builder().level(Level::Fatal).build());
+
+        let id = |level: Level| {
+            MetricId::new("logging.sls")
+                .with_tag("type", "service.1")
+                .with_tag("level", level.as_str())
+        };
+        assert_eq!(registry.meter(id(Level::Warn)).count(), 2);
+        assert_eq!(registry.meter(id(Level::Fatal)).count(), 1);
+        assert_eq!(registry.meter(id(Level::Info)).count(), 0);
+
+        match registry
+            .metrics()
+            .iter()
+            .find(|(id, _)| id.name() == "logging.level")
+        {
+            Some((_, Metric::Gauge(gauge))) => assert_eq!(
+                gauge.value(),
+                serde_value::to_value(witchcraft_log::max_level()).unwrap(),
+            ),
+            _ => panic!("gauge not registered"),
+        }
+    }
+
+    #[test]
+    fn disabled() {
+        let registry = MetricRegistry::new();
+        let logger = InstrumentedLogger::new(&registry, NopLogger(LevelFilter::Info));
+
+        logger.log(&Record::builder().level(Level::Info).build());
+        logger.log(&Record::builder().level(Level::Debug).build());
+
+        let id = |level: Level| {
+            MetricId::new("logging.sls")
+                .with_tag("type", "service.1")
+                .with_tag("level", level.as_str())
+        };
+        assert_eq!(registry.meter(id(Level::Info)).count(), 1);
+        assert_eq!(registry.meter(id(Level::Debug)).count(), 0);
+    }
+
+    #[test]
+    fn rules() {
+        let registry = MetricRegistry::new();
+        let mut logger = InstrumentedLogger::new(&registry, NopLogger(LevelFilter::Trace));
+
+        let mut rule = LogRule::new(&registry, "rule");
+        rule.set_max_level(LevelFilter::Warn)
+            .set_message_contains("failed")
+            .add_param("host", "foo");
+        logger.add_rule(rule);
+
+        logger.log(
+            &Record::builder()
+                .level(Level::Error)
+                .message("connection failed")
+                .safe_params(&[("host", &"foo"), ("port", &1)])
+                .build(),
+        );
+        // wrong level
+        logger.log(
+            &Record::builder()
+                .level(Level::Info)
+                .message("connection failed")
+                .safe_params(&[("host", &"foo")])
+                .build(),
+        );
+        // wrong message
+        logger.log(
+            &Record::builder()
+                .level(Level::Error)
+                .message("connection succeeded")
+                .safe_params(&[("host", &"foo")])
+                .build(),
+        );
+        // wrong param value
+        logger.log(
+            &Record::builder()
+                .level(Level::Error)
+                .message("connection failed")
+                .unsafe_params(&[("host", &"bar")])
+                .build(),
+        );
+        // unsafe params are checked too
+        logger.log(
+            &Record::builder()
+                .level(Level::Warn)
+                .message("connection failed")
+                .unsafe_params(&[("host", &"foo")])
+                .build(),
+        );
+
+        assert_eq!(registry.meter("rule").count(), 2);
+    }
+
+    #[test]
+    fn rules_skip_unneeded_params() {
+        let registry = MetricRegistry::new();
+        let mut logger = InstrumentedLogger::new(&registry, NopLogger(LevelFilter::Info));
+
+        let mut rule = LogRule::new(&registry, "rule");
+        rule.add_param("host", "foo");
+        logger.add_rule(rule);
+
+        let unused = witchcraft_log::lazy(|| -> &str { panic!("parameter serialized") });
+        logger.log(
+            &Record::builder()
+                .level(Level::Error)
+                .safe_params(&[("other", &unused), ("host", &"foo")])
+                .build(),
+        );
+        logger.log(
+            &Record::builder()
+                .level(Level::Debug)
+                .safe_params(&[("host", &unused)])
+                .build(),
+        );
+
+        assert_eq!(registry.meter("rule").count(), 1);
+    }
+}
@@ -0,0 +1,225 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{Clock, Gauge, MetricId, MetricRegistry};
+use parking_lot::Mutex;
+use serde_value::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+struct State {
+    next_id: u64,
+    tasks: HashMap<u64, Instant>,
+}
+
+struct Inner {
+    clock: Arc<dyn Clock>,
+    state: Mutex<State>,
+}
+
+impl Inner {
+    fn active_tasks(&self) -> usize {
+        self.state.lock().tasks.len()
+    }
+
+    fn durations(&self) -> impl Iterator<Item = Duration> {
+        let now = self.clock.now();
+        let starts = self
+            .state
+            .lock()
+            .tasks
+            .values()
+            .copied()
+            .collect::<Vec<_>>();
+        starts.into_iter().map(move |start| now - start)
+    }
+}
+
+// Registered as the `.active` gauge so that all timers with the same ID share it.
+impl Gauge for Inner {
+    fn value(&self) -> Value {
+        Value::U64(self.active_tasks() as u64)
+    }
+}
+
+/// A timer tracking operations which are currently in progress.
+///
+/// Unlike a [`Timer`](crate::Timer), which only records operations once they complete, a long task timer can show
+/// that an operation has been running for an unexpectedly long time while it is still running.
+///
+/// It registers the following gauges with the tags of its ID:
+///
+/// * `<name>.active` - the number of tasks currently running.
+/// * `<name>.duration` - the sum of the running times of the tasks currently running, in nanoseconds.
+/// * `<name>.max` - the running time of the longest running task currently running, in nanoseconds.
+///
+/// Timers created with the same ID in the same registry share their tasks.
+///
+/// # Examples
+///
+/// ```
+/// use witchcraft_metrics::{LongTaskTimer, MetricRegistry};
+///
+/// let registry = MetricRegistry::new();
+/// let timer = LongTaskTimer::new(&registry, "migration");
+///
+/// let _task = timer.start();
+/// // run the migration
+/// ```
+pub struct LongTaskTimer {
+    inner: Arc<Inner>,
+}
+
+impl LongTaskTimer {
+    /// Creates a new long task timer with the specified ID, registering its gauges in the registry.
+    ///
+    /// The timer uses the registry's clock as its time source.
+    pub fn new<T>(registry: &MetricRegistry, id: T) -> LongTaskTimer
+    where
+        T: Into<MetricId>,
+    {
+        let id = id.into();
+        let inner = registry
+            .typed_gauge_with(id.with_name_suffix("active"), || Inner {
+                clock: registry.clock().clone(),
+                state: Mutex::new(State {
+                    next_id: 0,
+                    tasks: HashMap::new(),
+                }),
+            })
+            .unwrap_or_else(|| panic!("gauge already registered for long task timer {:?}", id));
+
+        registry.gauge(id.with_name_suffix("duration"), {
+            let inner = inner.clone();
+            move || inner.durations().sum::<Duration>().as_nanos() as u64
+        });
+        registry.gauge(id.with_name_suffix("max"), {
+            let inner = inner.clone();
+            move || inner.durations().max().unwrap_or_default().as_nanos() as u64
+        });
+
+        LongTaskTimer { inner }
+    }
+
+    /// Starts a new task, returning a guard which marks the task as completed when it drops.
+    pub fn start(&self) -> LongTask<'_> {
+        let start = self.inner.clock.now();
+        let mut state = self.inner.state.lock();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.tasks.insert(id, start);
+
+        LongTask { timer: self, id }
+    }
+
+    /// Returns the number of tasks currently running.
+    pub fn active_tasks(&self) -> usize {
+        self.inner.active_tasks()
+    }
+
+    /// Returns the sum of the running times of the tasks currently running.
+    pub fn duration(&self) -> Duration {
+        self.inner.durations().sum()
+    }
+
+    /// Returns the running time of the longest running task currently running, or 0 if no tasks are running.
+    pub fn max_duration(&self) -> Duration {
+        self.inner.durations().max().unwrap_or_default()
+    }
+}
+
+/// A guard type representing a task in progress in a [`LongTaskTimer`].
+pub struct LongTask<'a> {
+    timer: &'a LongTaskTimer,
+    id: u64,
+}
+
+impl Drop for LongTask<'_> {
+    fn drop(&mut self) {
+        self.timer.inner.state.lock().tasks.remove(&self.id);
+    }
+}
+
+impl LongTask<'_> {
+    /// Returns the running time of the task so far.
+    pub fn duration(&self) -> Duration {
+        let start = self.timer.inner.state.lock().tasks[&self.id];
+        self.timer.inner.clock.now() - start
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{LongTaskTimer, ManualClock, MetricId, MetricRegistry};
+    use serde_value::Value;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn basic() {
+        let clock = Arc::new(ManualClock::new());
+        let mut registry = MetricRegistry::new();
+        registry.set_clock(clock.clone());
+
+        let timer = LongTaskTimer::new(&registry, MetricId::new("task").with_tag("foo", "bar"));
+        assert_eq!(timer.active_tasks(), 0);
+        assert_eq!(timer.max_duration(), Duration::from_secs(0));
+
+        let a = timer.start();
+        clock.advance(Duration::from_secs(2));
+        let b = timer.start();
+        clock.advance(Duration::from_secs(1));
+
+        assert_eq!(timer.active_tasks(), 2);
+        assert_eq!(timer.duration(), Duration::from_secs(4));
+        assert_eq!(timer.max_duration(), Duration::from_secs(3));
+        assert_eq!(a.duration(), Duration::from_secs(3));
+        assert_eq!(b.duration(), Duration::from_secs(1));
+
+        let max = registry.gauge(MetricId::new("task.max").with_tag("foo", "bar"), || 0);
+        assert_eq!(max.value(), Value::U64(3_000_000_000));
+
+        drop(a);
+        assert_eq!(timer.active_tasks(), 1);
+        assert_eq!(timer.max_duration(), Duration::from_secs(1));
+
+        drop(b);
+        let active = registry.gauge(MetricId::new("task.active").with_tag("foo", "bar"), || 0);
+        assert_eq!(active.value(), Value::U64(0));
+    }
+
+    #[test]
+    fn tasks_shared_between_timers() {
+        let clock = Arc::new(ManualClock::new());
+        let mut registry = MetricRegistry::new();
+        registry.set_clock(clock.clone());
+
+        let a = LongTaskTimer::new(&registry, "task");
+        let b = LongTaskTimer::new(&registry, "task");
+
+        let task_a = a.start();
+        clock.advance(Duration::from_secs(2));
+        let _task_b = b.start();
+        clock.advance(Duration::from_secs(1));
+
+        assert_eq!(a.active_tasks(), 2);
+        assert_eq!(b.duration(), Duration::from_secs(4));
+        assert_eq!(b.max_duration(), Duration::from_secs(3));
+
+        drop(task_a);
+        assert_eq!(b.active_tasks(), 1);
+        let active = registry.gauge("task.active", || 0);
+        assert_eq!(active.value(), Value::U64(1));
+    }
+}
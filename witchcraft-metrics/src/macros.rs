@@ -0,0 +1,213 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Creates a metric name from a sequence of string literal segments, joined by `.`.
+///
+/// The name is assembled at compile time, and it is a compile error for a segment to be empty or contain characters
+/// other than ASCII alphanumerics, `-` and `_`. A segment may contain `.`-separated sub-segments as long as they are
+/// also valid. Use the [`MetricName`](crate::MetricName) builder for names with segments only known at runtime.
+///
+/// # Examples
+///
+/// ```
+/// use witchcraft_metrics::metric_name;
+///
+/// assert_eq!(metric_name!("server", "response", "error"), "server.response.error");
+/// ```
+///
+/// ```compile_fail
+/// use witchcraft_metrics::metric_name;
+///
+/// let name = metric_name!("server", "response error");
+/// ```
+#[macro_export]
+macro_rules! metric_name {
+    ($first:literal $(, $rest:literal)* $(,)?) => {{
+        const NAME: &str = concat!($first $(, ".", $rest)*);
+        const _: () = assert!($crate::private::valid_metric_name(NAME), "invalid metric name");
+        NAME
+    }};
+}
+
+/// Creates a [`BuildInfo`](crate::BuildInfo) describing the calling crate.
+///
+/// The name and version are taken from the crate's Cargo metadata. The Git commit and build time are taken from the
+/// `GIT_SHA` and `BUILD_TIME` environment variables at compile time if they are set, for example by a build script
+/// emitting `cargo:rustc-env=GIT_SHA=...`.
+///
+/// # Examples
+///
+/// ```
+/// use witchcraft_metrics::{build_info, MetricRegistry};
+///
+/// let registry = MetricRegistry::new();
+/// build_info!().register(&registry);
+/// ```
+#[macro_export]
+macro_rules! build_info {
+    () => {
+        $crate::private::build_info(
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+            option_env!("GIT_SHA"),
+            option_env!("BUILD_TIME"),
+        )
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __global_metric {
+    ($ty:ty, $method:ident, $name:literal $(, $key:literal => $value:literal)* $(,)?) => {{
+        static CALLSITE: $crate::private::CallSite<$ty> = $crate::private::CallSite::new();
+        CALLSITE.get(|registry| registry.$method($crate::MetricId::new($name) $(.with_tag($key, $value))*))
+    }};
+    ($ty:ty, $method:ident, $name:expr $(, $key:expr => $value:expr)* $(,)?) => {
+        $crate::global().$method($crate::MetricId::new($name) $(.with_tag($key, $value))*)
+    };
+}
+
+/// Returns the [`Counter`](crate::Counter) with the specified name and tags from the [global registry](crate::global).
+///
+/// If the name and tags are all literals, the counter is looked up once and cached at the call site after the global
+/// registry has been set. Otherwise, it is looked up on each call. This applies to all of the metric macros.
+///
+/// # Panics
+///
+/// Panics if a metric of a different type is registered with the same ID. This applies to all of the metric macros.
+///
+/// # Examples
+///
+/// ```
+/// use witchcraft_metrics::counter;
+///
+/// counter!("shavings", "animal" => "yak").inc();
+///
+/// let animal = String::from("goat");
+/// counter!("shavings", "animal" => animal).inc();
+/// ```
+#[macro_export]
+macro_rules! counter {
+    ($($v:tt)*) => {
+        $crate::__global_metric!(::std::sync::Arc<$crate::Counter>, counter, $($v)*)
+    };
+}
+
+/// Returns the meter with the specified name and tags from the [global registry](crate::global).
+///
+/// # Examples
+///
+/// ```
+/// use witchcraft_metrics::meter;
+///
+/// meter!("server.requests", "endpoint" => "getFoo").mark(1);
+/// ```
+#[macro_export]
+macro_rules! meter {
+    ($($v:tt)*) => {
+        $crate::__global_metric!(::std::sync::Arc<dyn $crate::Metered>, meter, $($v)*)
+    };
+}
+
+/// Returns the [`Histogram`](crate::Histogram) with the specified name and tags from the
+/// [global registry](crate::global).
+///
+/// # Examples
+///
+/// ```
+/// use witchcraft_metrics::histogram;
+///
+/// histogram!("server.response.size").update(1024);
+/// ```
+#[macro_export]
+macro_rules! histogram {
+    ($($v:tt)*) => {
+        $crate::__global_metric!(::std::sync::Arc<$crate::Histogram>, histogram, $($v)*)
+    };
+}
+
+/// Returns the [`Timer`](crate::Timer) with the specified name and tags from the [global registry](crate::global).
+///
+/// # Examples
+///
+/// ```
+/// use witchcraft_metrics::timer;
+///
+/// let timer = timer!("server.requests", "endpoint" => "getFoo");
+/// let _time = timer.time();
+/// ```
+#[macro_export]
+macro_rules! timer {
+    ($($v:tt)*) => {
+        $crate::__global_metric!(::std::sync::Arc<$crate::Timer>, timer, $($v)*)
+    };
+}
+
+/// Asserts that a property of a metric in a registry satisfies a comparison.
+///
+/// The metric is identified by anything convertible to a [`MetricId`](crate::MetricId), and the comparison has the form
+/// `<property> <operator> <value>`. The supported properties are:
+///
+/// * `count` - the count of a counter, meter, histogram, or timer, as an `i64`.
+/// * `value` - the value of a gauge, as a `serde_value::Value`.
+/// * `min`, `max` - the minimum or maximum of a histogram or timer's snapshot, as an `i64`.
+/// * `mean` - the mean of a histogram or timer's snapshot, as an `f64`.
+///
+/// This is intended for use in tests.
+///
+/// # Panics
+///
+/// Panics if the comparison fails, if no metric is registered with the ID, or if the metric doesn't have the
+/// property.
+///
+/// # Examples
+///
+/// ```
+/// use witchcraft_metrics::{assert_metric, MetricId, MetricRegistry};
+///
+/// let registry = MetricRegistry::new();
+/// registry.meter("server.requests").mark(5);
+/// registry.histogram(MetricId::new("server.response.size").with_tag("endpoint", "getFoo")).update(1024);
+///
+/// assert_metric!(registry, "server.requests", count >= 5);
+/// assert_metric!(registry, MetricId::new("server.response.size").with_tag("endpoint", "getFoo"), max == 1024);
+/// ```
+///
+/// ```should_panic
+/// use witchcraft_metrics::{assert_metric, MetricRegistry};
+///
+/// let registry = MetricRegistry::new();
+/// registry.counter("server.errors").inc();
+///
+/// assert_metric!(registry, "server.errors", count == 0);
+/// ```
+#[macro_export]
+macro_rules! assert_metric {
+    ($registry:expr, $id:expr, $property:ident $op:tt $expected:expr $(,)?) => {{
+        let metric = $crate::private::metric_assert(&$registry, $id);
+        let actual = metric.$property();
+        let expected = $expected;
+        let passed = actual $op expected;
+        if !passed {
+            panic!(
+                "assertion failed: `{} {} {:?}` for metric {:?} (actual: {:?})",
+                stringify!($property),
+                stringify!($op),
+                expected,
+                metric.id(),
+                actual,
+            );
+        }
+    }};
+}
@@ -13,16 +13,106 @@
 // limitations under the License.
 use crate::Clock;
 use parking_lot::Mutex;
+use std::any::TypeId;
 use std::convert::TryFrom;
 use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 const INTERVAL_SECS: u64 = 5;
+// every tick is at least INTERVAL_SECS after the previous one, so a shorter max gap would flag every tick
+const MIN_GAP_SECS: u64 = 2 * INTERVAL_SECS;
 const SECONDS_PER_MINUTE: f64 = 60.;
 
+mod private {
+    pub struct PrivacyToken;
+}
+
+/// A metric tracking the rate of occurrence of an event.
+///
+/// This is implemented by [`Meter`], and allows applications to register alternate rate estimators in a
+/// [`MetricRegistry`](crate::MetricRegistry).
+pub trait Metered: 'static + Sync + Send {
+    /// Mark the occurrence of `n` event(s).
+    fn mark(&self, n: i64);
+
+    /// Returns the number of events registered by the meter.
+    fn count(&self) -> i64;
+
+    /// Returns the one minute rolling average rate of the occurrence of events measured in events per second.
+    fn one_minute_rate(&self) -> f64;
+
+    /// Returns the five minute rolling average rate of the occurrence of events measured in events per second.
+    fn five_minute_rate(&self) -> f64;
+
+    /// Returns the fifteen minute rolling average rate of the occurrence of events measured in events per second.
+    fn fifteen_minute_rate(&self) -> f64;
+
+    /// Returns the mean rate of the occurrence of events since the creation of the meter measured in events per second.
+    fn mean_rate(&self) -> f64;
+
+    // See the comment on Gauge::__private_api_type_id.
+    #[doc(hidden)]
+    fn __private_api_type_id(&self, _: private::PrivacyToken) -> TypeId {
+        TypeId::of::<Self>()
+    }
+}
+
+impl dyn Metered {
+    /// Returns `true` if the meter's type is `T`.
+    pub fn is<T>(&self) -> bool
+    where
+        T: Metered,
+    {
+        self.__private_api_type_id(private::PrivacyToken) == TypeId::of::<T>()
+    }
+
+    /// Attempts to downcast the meter to the type `T` if it has that type.
+    pub fn downcast_ref<T>(&self) -> Option<&T>
+    where
+        T: Metered,
+    {
+        if self.is::<T>() {
+            unsafe { Some(&*(self as *const dyn Metered as *const T)) }
+        } else {
+            None
+        }
+    }
+
+    /// Attempts to downcast the meter to the type `T` if it has that type.
+    pub fn downcast_arc<T>(self: Arc<Self>) -> Result<Arc<T>, Arc<Self>>
+    where
+        T: Metered,
+    {
+        if self.is::<T>() {
+            unsafe { Ok(Arc::from_raw(Arc::into_raw(self).cast::<T>())) }
+        } else {
+            Err(self)
+        }
+    }
+}
+
+/// The behavior of a [`Meter`] when it detects an abnormally long gap between ticks of its rolling averages.
+///
+/// Such gaps can be caused by the host suspending or by the monotonic clock jumping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GapPolicy {
+    /// Decay the rolling averages as if no events occurred during the gap.
+    Decay,
+    /// Reset the rolling averages, discarding the rate history from before the gap.
+    ///
+    /// Events marked during the gap still contribute to the meter's count, but not its rolling averages.
+    Reset,
+}
+
+struct GapDetection {
+    max_gap_secs: u64,
+    policy: GapPolicy,
+}
+
 struct State {
     count: i64,
+    warm_up_ticks: u64,
     rate_10s: Ewma,
     rate_30s: Ewma,
     rate_1m: Ewma,
@@ -38,6 +128,8 @@ pub struct Meter {
     last_tick: AtomicU64,
     start_time: Instant,
     clock: Arc<dyn Clock>,
+    gap_detection: Option<GapDetection>,
+    detected_gaps: AtomicU64,
     state: Mutex<State>,
 }
 
@@ -55,13 +147,39 @@ impl Meter {
 
     /// Creates a new meter using the provided [`Clock`] as its time source.
     pub fn new_with(clock: Arc<dyn Clock>) -> Meter {
+        Meter::new_inner(clock, None)
+    }
+
+    /// Creates a new meter using the provided [`Clock`] as its time source, which handles gaps between ticks of at
+    /// least `max_gap` according to the provided [`GapPolicy`].
+    ///
+    /// The meter ticks its rolling averages every 5 seconds when it is marked or its rates are read, so `max_gap` should
+    /// be comfortably larger than the expected interval between those operations. It is rounded up to a whole number
+    /// of seconds, and values below 10 seconds are raised to 10 seconds.
+    pub fn new_with_gap_policy(
+        clock: Arc<dyn Clock>,
+        max_gap: Duration,
+        policy: GapPolicy,
+    ) -> Meter {
+        let max_gap_secs = max_gap.as_secs() + u64::from(max_gap.subsec_nanos() > 0);
+        let gap_detection = GapDetection {
+            max_gap_secs: max_gap_secs.max(MIN_GAP_SECS),
+            policy,
+        };
+        Meter::new_inner(clock, Some(gap_detection))
+    }
+
+    fn new_inner(clock: Arc<dyn Clock>, gap_detection: Option<GapDetection>) -> Meter {
         Meter {
             uncounted: AtomicI64::new(0),
             last_tick: AtomicU64::new(0),
             start_time: clock.now(),
             clock,
+            gap_detection,
+            detected_gaps: AtomicU64::new(0),
             state: Mutex::new(State {
                 count: 0,
+                warm_up_ticks: 0,
                 rate_10s: Ewma::new(0.16),
                 rate_30s: Ewma::new(0.5),
                 rate_1m: Ewma::new(1.),
@@ -123,6 +241,27 @@ impl Meter {
         }
     }
 
+    /// Returns `true` if the meter's rolling averages cover at least `window` of history.
+    ///
+    /// A rolling average is dominated by its first few ticks until it has been running for about its own window, so
+    /// for example the one minute rate can be misleading until `meter.warmed_up(Duration::from_secs(60))`. History is
+    /// counted from the creation of the meter, or the last gap handled by [`GapPolicy::Reset`].
+    pub fn warmed_up(&self, window: Duration) -> bool {
+        self.tick_if_necessary();
+        self.state
+            .lock()
+            .warm_up_ticks
+            .saturating_mul(INTERVAL_SECS)
+            >= window.as_secs()
+    }
+
+    /// Returns the number of abnormally long gaps between ticks detected by the meter.
+    ///
+    /// This is always 0 unless the meter was created with [`Meter::new_with_gap_policy`].
+    pub fn detected_gaps(&self) -> u64 {
+        self.detected_gaps.load(Ordering::Relaxed)
+    }
+
     fn tick_if_necessary(&self) {
         let time = self.clock.now();
         let old_tick = self.last_tick.load(Ordering::SeqCst);
@@ -154,6 +293,24 @@ impl Meter {
         let uncounted = self.uncounted.swap(0, Ordering::SeqCst);
         state.count += uncounted;
 
+        if let Some(gap_detection) = &self.gap_detection {
+            if age >= gap_detection.max_gap_secs {
+                self.detected_gaps.fetch_add(1, Ordering::Relaxed);
+
+                if gap_detection.policy == GapPolicy::Reset {
+                    state.warm_up_ticks = 0;
+                    state.rate_10s.reset();
+                    state.rate_30s.reset();
+                    state.rate_1m.reset();
+                    state.rate_5m.reset();
+                    state.rate_15m.reset();
+                    return;
+                }
+            }
+        }
+
+        state.warm_up_ticks = state.warm_up_ticks.saturating_add(required_ticks);
+
         state.rate_10s.tick(uncounted);
         state.rate_10s.decay(required_ticks - 1);
 
@@ -171,6 +328,38 @@ impl Meter {
     }
 }
 
+impl Metered for Meter {
+    #[inline]
+    fn mark(&self, n: i64) {
+        self.mark(n)
+    }
+
+    #[inline]
+    fn count(&self) -> i64 {
+        self.count()
+    }
+
+    #[inline]
+    fn one_minute_rate(&self) -> f64 {
+        self.one_minute_rate()
+    }
+
+    #[inline]
+    fn five_minute_rate(&self) -> f64 {
+        self.five_minute_rate()
+    }
+
+    #[inline]
+    fn fifteen_minute_rate(&self) -> f64 {
+        self.fifteen_minute_rate()
+    }
+
+    #[inline]
+    fn mean_rate(&self) -> f64 {
+        self.mean_rate()
+    }
+}
+
 // Modeled after Java metrics-core's EWMA.java
 struct Ewma {
     rate: f64,
@@ -212,6 +401,11 @@ impl Ewma {
         }
     }
 
+    fn reset(&mut self) {
+        self.rate = 0.;
+        self.initialized = false;
+    }
+
     fn get(&self) -> f64 {
         self.rate
     }
@@ -219,8 +413,7 @@ impl Ewma {
 
 #[cfg(test)]
 mod test {
-    use crate::clock::test::TestClock;
-    use crate::Meter;
+    use crate::{GapPolicy, ManualClock, Meter, Metered};
     use assert_approx_eq::assert_approx_eq;
     use std::sync::Arc;
     use std::time::Duration;
@@ -228,7 +421,7 @@ mod test {
     #[test]
     #[allow(clippy::float_cmp)]
     fn starts_out_with_no_rates_or_count() {
-        let clock = Arc::new(TestClock::new());
+        let clock = Arc::new(ManualClock::new());
         let meter = Meter::new_with(clock);
 
         assert_eq!(meter.count(), 0);
@@ -240,7 +433,7 @@ mod test {
 
     #[test]
     fn marks_events_and_updates_rate_and_count() {
-        let clock = Arc::new(TestClock::new());
+        let clock = Arc::new(ManualClock::new());
         let meter = Meter::new_with(clock.clone());
 
         meter.mark(1);
@@ -253,4 +446,90 @@ mod test {
         assert_approx_eq!(meter.five_minute_rate(), 0.1966, 0.001);
         assert_approx_eq!(meter.fifteen_minute_rate(), 0.1988, 0.001);
     }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn gap_reset() {
+        let clock = Arc::new(ManualClock::new());
+        let meter =
+            Meter::new_with_gap_policy(clock.clone(), Duration::from_secs(60), GapPolicy::Reset);
+
+        meter.mark(10);
+        clock.advance(Duration::from_secs(10));
+        assert!(meter.one_minute_rate() > 0.);
+        assert_eq!(meter.detected_gaps(), 0);
+
+        meter.mark(10);
+        clock.advance(Duration::from_secs(3600));
+        assert_eq!(meter.one_minute_rate(), 0.);
+        assert_eq!(meter.count(), 20);
+        assert_eq!(meter.detected_gaps(), 1);
+
+        meter.mark(5);
+        clock.advance(Duration::from_secs(5));
+        assert_approx_eq!(meter.one_minute_rate(), 1., 0.001);
+        assert_eq!(meter.detected_gaps(), 1);
+    }
+
+    #[test]
+    fn gap_decay() {
+        let clock = Arc::new(ManualClock::new());
+        let meter =
+            Meter::new_with_gap_policy(clock.clone(), Duration::from_secs(60), GapPolicy::Decay);
+
+        meter.mark(10);
+        clock.advance(Duration::from_secs(10));
+        let rate = meter.fifteen_minute_rate();
+
+        clock.advance(Duration::from_secs(120));
+        assert!(meter.fifteen_minute_rate() < rate);
+        assert!(meter.fifteen_minute_rate() > 0.);
+        assert_eq!(meter.detected_gaps(), 1);
+    }
+
+    #[test]
+    fn short_max_gap() {
+        let clock = Arc::new(ManualClock::new());
+        let meter =
+            Meter::new_with_gap_policy(clock.clone(), Duration::from_millis(500), GapPolicy::Reset);
+
+        meter.mark(5);
+        clock.advance(Duration::from_secs(5));
+        assert_approx_eq!(meter.one_minute_rate(), 1., 0.001);
+
+        clock.advance(Duration::from_secs(5));
+        assert!(meter.one_minute_rate() > 0.);
+        assert_eq!(meter.detected_gaps(), 0);
+
+        clock.advance(Duration::from_millis(10_500));
+        assert_eq!(meter.one_minute_rate(), 0.);
+        assert_eq!(meter.detected_gaps(), 1);
+    }
+
+    #[test]
+    fn warmed_up() {
+        let clock = Arc::new(ManualClock::new());
+        let meter =
+            Meter::new_with_gap_policy(clock.clone(), Duration::from_secs(60), GapPolicy::Reset);
+
+        assert!(meter.warmed_up(Duration::from_secs(0)));
+        assert!(!meter.warmed_up(Duration::from_secs(10)));
+
+        clock.advance(Duration::from_secs(10));
+        assert!(meter.warmed_up(Duration::from_secs(10)));
+        assert!(!meter.warmed_up(Duration::from_secs(15)));
+
+        clock.advance(Duration::from_secs(3600));
+        assert!(!meter.warmed_up(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn downcast() {
+        let meter: Arc<dyn Metered> = Arc::new(Meter::new());
+        meter.mark(2);
+
+        assert!(meter.is::<Meter>());
+        assert_eq!(meter.downcast_ref::<Meter>().unwrap().count(), 2);
+        assert_eq!(meter.downcast_arc::<Meter>().ok().unwrap().count(), 2);
+    }
 }
@@ -56,6 +56,14 @@ impl MetricId {
     pub fn tags(&self) -> &Tags {
         &self.tags
     }
+
+    /// Returns a new ID with the same tags and `.<suffix>` appended to the name.
+    pub(crate) fn with_name_suffix(&self, suffix: &str) -> MetricId {
+        MetricId {
+            name: Cow::Owned(format!("{}.{}", self.name, suffix)),
+            tags: self.tags.clone(),
+        }
+    }
 }
 
 impl From<String> for MetricId {
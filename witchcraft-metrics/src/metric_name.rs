@@ -0,0 +1,168 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::private::valid_metric_name;
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+
+/// A builder of metric names from conventional segments.
+///
+/// Names are built as `<service>.<segment>*.<endpoint>.<outcome>`, joined by `.`, regardless of the order the builder
+/// methods are called in. Segments are validated when the name is built with the same rules as the
+/// [`metric_name!`](crate::metric_name) macro, which should be preferred when every segment is a literal.
+///
+/// # Examples
+///
+/// ```
+/// use witchcraft_metrics::MetricName;
+///
+/// let endpoint = "getFoo";
+/// let name = MetricName::new("server")
+///     .outcome("success")
+///     .endpoint(endpoint)
+///     .build()
+///     .unwrap();
+/// assert_eq!(name, "server.getFoo.success");
+///
+/// assert!(MetricName::new("server").endpoint("get foo").build().is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MetricName {
+    service: Cow<'static, str>,
+    segments: Vec<Cow<'static, str>>,
+    endpoint: Option<Cow<'static, str>>,
+    outcome: Option<Cow<'static, str>>,
+}
+
+impl MetricName {
+    /// Creates a new builder with the specified service segment.
+    pub fn new<T>(service: T) -> MetricName
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        MetricName {
+            service: service.into(),
+            segments: vec![],
+            endpoint: None,
+            outcome: None,
+        }
+    }
+
+    /// Appends a segment following the service and any previously added segments.
+    pub fn segment<T>(mut self, segment: T) -> MetricName
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.segments.push(segment.into());
+        self
+    }
+
+    /// Sets the endpoint segment, which follows the service and other segments.
+    pub fn endpoint<T>(mut self, endpoint: T) -> MetricName
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Sets the outcome segment, which is always last.
+    pub fn outcome<T>(mut self, outcome: T) -> MetricName
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.outcome = Some(outcome.into());
+        self
+    }
+
+    /// Builds the metric name.
+    ///
+    /// Returns an error if a segment is empty, or contains characters other than ASCII alphanumerics, `-`, `_`, and
+    /// `.` between valid sub-segments.
+    pub fn build(self) -> Result<String, InvalidMetricNameError> {
+        let segments = Some(self.service)
+            .into_iter()
+            .chain(self.segments)
+            .chain(self.endpoint)
+            .chain(self.outcome);
+
+        let mut name = String::new();
+        for segment in segments {
+            if !valid_metric_name(&segment) {
+                return Err(InvalidMetricNameError {
+                    segment: segment.into_owned(),
+                });
+            }
+
+            if !name.is_empty() {
+                name.push('.');
+            }
+            name.push_str(&segment);
+        }
+
+        Ok(name)
+    }
+}
+
+/// An error building a [`MetricName`] with an invalid segment.
+#[derive(Debug)]
+pub struct InvalidMetricNameError {
+    segment: String,
+}
+
+impl InvalidMetricNameError {
+    /// Returns the invalid segment.
+    pub fn segment(&self) -> &str {
+        &self.segment
+    }
+}
+
+impl fmt::Display for InvalidMetricNameError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "invalid metric name segment `{}`", self.segment)
+    }
+}
+
+impl Error for InvalidMetricNameError {}
+
+#[cfg(test)]
+mod test {
+    use crate::MetricName;
+
+    #[test]
+    fn build() {
+        let name = MetricName::new("server")
+            .outcome("error")
+            .endpoint(String::from("getFoo"))
+            .segment("response")
+            .segment("http.status")
+            .build()
+            .unwrap();
+        assert_eq!(name, "server.response.http.status.getFoo.error");
+
+        assert_eq!(
+            MetricName::new("server").build().unwrap(),
+            metric_name!("server")
+        );
+
+        let err = MetricName::new("server").endpoint("").build().unwrap_err();
+        assert_eq!(err.segment(), "");
+        let err = MetricName::new("server")
+            .segment("a..b")
+            .build()
+            .unwrap_err();
+        assert_eq!(err.segment(), "a..b");
+        assert!(MetricName::new("server response").build().is_err());
+    }
+}
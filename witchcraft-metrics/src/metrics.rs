@@ -0,0 +1,259 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Support for recording metrics from the `metrics` crate into a `MetricRegistry`.
+//!
+//! Requires the `metrics` Cargo feature.
+//!
+//! Libraries instrumented with the `metrics` crate's macros report to a globally installed `metrics::Recorder`.
+//! `RegistryRecorder` is a recorder which maps those metrics into a `MetricRegistry`:
+//!
+//! * Counters are registered as `Counter`s.
+//! * Gauges are registered as gauges reporting an `f64`.
+//! * Histograms described with a time unit via `metrics::describe_histogram!` are registered as `Timer`s, with
+//!   values converted from that unit. This preserves fractional durations such as
+//!   `histogram!("request.duration").record(elapsed.as_secs_f64())`. Negative and NaN durations are ignored.
+//! * Other histograms are registered as `Histogram`s. Since `Histogram`s track integers, values are rounded to the
+//!   nearest integer when recorded, so fractional values should be recorded in a smaller unit or described with a
+//!   time unit.
+//!
+//! The key's name is used as the metric name, and its labels as the metric's tags. Histograms should be described
+//! before they are first recorded, since a histogram already registered as a `Histogram` stays one.
+//!
+//! Like the registry's own methods, recording a metric panics if a different kind of metric is already registered
+//! with the same ID.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::sync::Arc;
+//! use witchcraft_metrics::metrics::RegistryRecorder;
+//! use witchcraft_metrics::MetricRegistry;
+//!
+//! let registry = Arc::new(MetricRegistry::new());
+//! let _ = metrics::set_global_recorder(RegistryRecorder::new(registry.clone()));
+//!
+//! metrics::counter!("requests", "endpoint" => "getFoo").increment(1);
+//!
+//! metrics::describe_histogram!("request.duration", metrics::Unit::Seconds, "request latency");
+//! metrics::histogram!("request.duration").record(0.25);
+//! ```
+
+use crate::timer::f64_duration;
+use crate::{Counter, F64Gauge, Histogram, Metric, MetricId, MetricRegistry, Timer};
+use metrics::{
+    CounterFn, GaugeFn, HistogramFn, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+/// A `metrics::Recorder` implementation which registers metrics in a `MetricRegistry`.
+pub struct RegistryRecorder {
+    registry: Arc<MetricRegistry>,
+    histogram_units: Mutex<HashMap<String, Unit>>,
+}
+
+impl RegistryRecorder {
+    /// Creates a new recorder which registers metrics in the provided registry.
+    pub fn new(registry: Arc<MetricRegistry>) -> RegistryRecorder {
+        RegistryRecorder {
+            registry,
+            histogram_units: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the registry metrics are recorded into.
+    pub fn registry(&self) -> &Arc<MetricRegistry> {
+        &self.registry
+    }
+}
+
+fn nanos_per_unit(unit: Unit) -> Option<f64> {
+    match unit {
+        Unit::Seconds => Some(1e9),
+        Unit::Milliseconds => Some(1e6),
+        Unit::Microseconds => Some(1e3),
+        Unit::Nanoseconds => Some(1.),
+        _ => None,
+    }
+}
+
+fn cvt_key(key: &Key) -> MetricId {
+    key.labels()
+        .fold(MetricId::new(key.name().to_string()), |id, label| {
+            id.with_tag(label.key().to_string(), label.value().to_string())
+        })
+}
+
+impl Recorder for RegistryRecorder {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_histogram(&self, key: KeyName, unit: Option<Unit>, _: SharedString) {
+        if let Some(unit) = unit {
+            self.histogram_units
+                .lock()
+                .insert(key.as_str().to_string(), unit);
+        }
+    }
+
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> metrics::Counter {
+        metrics::Counter::from_arc(self.registry.counter(cvt_key(key)))
+    }
+
+    fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> metrics::Gauge {
+        let id = cvt_key(key);
+        match self
+            .registry
+            .gauge_with(id, F64Gauge::default)
+            .downcast_arc::<F64Gauge>()
+        {
+            Ok(gauge) => metrics::Gauge::from_arc(gauge),
+            // someone else registered a gauge with this ID, so there's nothing for us to update
+            Err(_) => metrics::Gauge::noop(),
+        }
+    }
+
+    fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> metrics::Histogram {
+        let id = cvt_key(key);
+        let nanos_per_unit = self
+            .histogram_units
+            .lock()
+            .get(key.name())
+            .and_then(|unit| nanos_per_unit(*unit));
+
+        match nanos_per_unit {
+            // a histogram recorded before it was described stays a histogram
+            Some(nanos_per_unit)
+                if !matches!(self.registry.get(id.clone()), Some(Metric::Histogram(_))) =>
+            {
+                metrics::Histogram::from_arc(Arc::new(UnitTimer {
+                    timer: self.registry.timer(id),
+                    nanos_per_unit,
+                }))
+            }
+            _ => metrics::Histogram::from_arc(self.registry.histogram(id)),
+        }
+    }
+}
+
+impl CounterFn for Counter {
+    fn increment(&self, value: u64) {
+        self.inc_by(value);
+    }
+
+    fn absolute(&self, value: u64) {
+        self.raise_to(i64::try_from(value).unwrap_or(i64::MAX));
+    }
+}
+
+impl HistogramFn for Histogram {
+    fn record(&self, value: f64) {
+        self.update(value.round() as i64);
+    }
+
+    fn record_many(&self, value: f64, count: usize) {
+        self.update_n(value.round() as i64, count as u64);
+    }
+}
+
+struct UnitTimer {
+    timer: Arc<Timer>,
+    nanos_per_unit: f64,
+}
+
+impl HistogramFn for UnitTimer {
+    fn record(&self, value: f64) {
+        if let Some(duration) = f64_duration(value, self.nanos_per_unit) {
+            self.timer.update(duration);
+        }
+    }
+
+    fn record_many(&self, value: f64, count: usize) {
+        if let Some(duration) = f64_duration(value, self.nanos_per_unit) {
+            self.timer.update_n(duration, count as u64);
+        }
+    }
+}
+
+impl GaugeFn for F64Gauge {
+    fn increment(&self, value: f64) {
+        self.add(value);
+    }
+
+    fn decrement(&self, value: f64) {
+        self.sub(value);
+    }
+
+    fn set(&self, value: f64) {
+        self.set(value);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Metric;
+    use serde_value::Value;
+
+    #[test]
+    fn basic() {
+        let registry = Arc::new(MetricRegistry::new());
+        let recorder = RegistryRecorder::new(registry.clone());
+
+        metrics::with_local_recorder(&recorder, || {
+            metrics::counter!("counter", "tag" => "value").increment(2);
+            metrics::counter!("counter", "tag" => "value").absolute(5);
+            metrics::counter!("counter", "tag" => "value").absolute(3);
+            metrics::gauge!("gauge").set(1.5);
+            metrics::gauge!("gauge").increment(1.);
+            metrics::histogram!("histogram").record(2.6);
+
+            metrics::describe_histogram!("timer", metrics::Unit::Seconds, "");
+            metrics::histogram!("timer").record(0.12);
+            metrics::histogram!("timer").record(0.45);
+            metrics::histogram!("timer").record(-1.);
+            metrics::histogram!("timer").record_many(0.3, 3);
+
+            metrics::histogram!("late").record(1.);
+            metrics::describe_histogram!("late", metrics::Unit::Milliseconds, "");
+            metrics::histogram!("late").record(2.);
+        });
+
+        let counter = registry.counter(MetricId::new("counter").with_tag("tag", "value"));
+        assert_eq!(counter.count(), 5);
+
+        match registry
+            .metrics()
+            .iter()
+            .find(|(id, _)| id.name() == "gauge")
+        {
+            Some((_, Metric::Gauge(gauge))) => assert_eq!(gauge.value(), Value::F64(2.5)),
+            _ => panic!("gauge not registered"),
+        }
+
+        let histogram = registry.histogram("histogram");
+        assert_eq!(histogram.count(), 1);
+        assert_eq!(histogram.snapshot().max(), 3);
+
+        let timer = registry.timer("timer");
+        assert_eq!(timer.count(), 5);
+        assert_eq!(timer.snapshot().min(), 120_000_000);
+        assert_eq!(timer.snapshot().max(), 450_000_000);
+
+        assert_eq!(registry.histogram("late").count(), 2);
+    }
+}
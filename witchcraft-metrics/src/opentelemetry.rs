@@ -0,0 +1,466 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Support for recording OpenTelemetry metrics into a `MetricRegistry`.
+//!
+//! Requires the `opentelemetry` Cargo feature.
+//!
+//! `RegistryMeterProvider` implements the OpenTelemetry metrics API on top of a `MetricRegistry`, so code
+//! instrumented against OpenTelemetry reports into the same registry as everything else:
+//!
+//! * Integer counters and up-down counters are registered as `Counter`s.
+//! * Floating point counters and up-down counters are registered as `F64Counter`s.
+//! * Gauges are registered as gauges reporting an `f64`.
+//! * Histograms with a time unit (`s`, `ms`, `us`, or `ns`) are registered as `Timer`s, with measurements converted
+//!   from that unit. Negative and NaN durations are ignored.
+//! * Other histograms are registered as `Histogram`s. Since `Histogram`s track integers, floating point measurements
+//!   recorded to them are rounded to the nearest integer.
+//!
+//! The instrument's name is used as the metric name, and the attributes of each measurement as the metric's tags.
+//! The instrumentation scope, descriptions, and histogram boundaries are ignored.
+//!
+//! If a metric of a different type is already registered with the ID of a measurement, for example by code using the
+//! registry directly, the measurement is dropped. A warning is logged the first time this happens for an instrument.
+//!
+//! Observable (callback-based) instruments are not supported, and are created as no-ops. Register a `Gauge` with the
+//! registry directly instead.
+//!
+//! # Examples
+//!
+//! ```
+//! use opentelemetry::metrics::MeterProvider;
+//! use opentelemetry::KeyValue;
+//! use std::sync::Arc;
+//! use witchcraft_metrics::opentelemetry::RegistryMeterProvider;
+//! use witchcraft_metrics::MetricRegistry;
+//!
+//! let registry = Arc::new(MetricRegistry::new());
+//! let provider = RegistryMeterProvider::new(registry.clone());
+//!
+//! let meter = provider.meter("my-library");
+//! let requests = meter.u64_counter("requests").build();
+//! requests.add(1, &[KeyValue::new("endpoint", "getFoo")]);
+//! ```
+
+use crate::timer::f64_duration;
+use crate::{
+    Counter as RegistryCounter, ExponentiallyDecayingReservoir, F64Counter, F64Gauge,
+    Histogram as RegistryHistogram, Metric, MetricId, MetricRegistry, Timer,
+};
+use opentelemetry::metrics::{
+    Counter, Gauge, Histogram, HistogramBuilder, InstrumentBuilder, InstrumentProvider, Meter,
+    MeterProvider, SyncInstrument, UpDownCounter,
+};
+use opentelemetry::{InstrumentationScope, KeyValue};
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// An OpenTelemetry `MeterProvider` implementation which registers metrics in a `MetricRegistry`.
+pub struct RegistryMeterProvider {
+    registry: Arc<MetricRegistry>,
+}
+
+impl RegistryMeterProvider {
+    /// Creates a new provider which registers metrics in the provided registry.
+    pub fn new(registry: Arc<MetricRegistry>) -> RegistryMeterProvider {
+        RegistryMeterProvider { registry }
+    }
+
+    /// Returns the registry metrics are recorded into.
+    pub fn registry(&self) -> &Arc<MetricRegistry> {
+        &self.registry
+    }
+}
+
+impl MeterProvider for RegistryMeterProvider {
+    fn meter_with_scope(&self, _: InstrumentationScope) -> Meter {
+        Meter::new(Arc::new(RegistryInstrumentProvider {
+            registry: self.registry.clone(),
+        }))
+    }
+}
+
+struct RegistryInstrumentProvider {
+    registry: Arc<MetricRegistry>,
+}
+
+impl RegistryInstrumentProvider {
+    fn instrument(&self, name: Cow<'static, str>) -> Arc<Instrument> {
+        Arc::new(Instrument {
+            registry: self.registry.clone(),
+            name,
+            conflict_warned: AtomicBool::new(false),
+        })
+    }
+}
+
+impl InstrumentProvider for RegistryInstrumentProvider {
+    fn u64_counter(&self, builder: InstrumentBuilder<'_, Counter<u64>>) -> Counter<u64> {
+        Counter::new(self.instrument(builder.name))
+    }
+
+    fn f64_counter(&self, builder: InstrumentBuilder<'_, Counter<f64>>) -> Counter<f64> {
+        Counter::new(Arc::new(F64CounterInstrument(
+            self.instrument(builder.name),
+        )))
+    }
+
+    fn i64_up_down_counter(
+        &self,
+        builder: InstrumentBuilder<'_, UpDownCounter<i64>>,
+    ) -> UpDownCounter<i64> {
+        UpDownCounter::new(self.instrument(builder.name))
+    }
+
+    fn f64_up_down_counter(
+        &self,
+        builder: InstrumentBuilder<'_, UpDownCounter<f64>>,
+    ) -> UpDownCounter<f64> {
+        UpDownCounter::new(Arc::new(F64CounterInstrument(
+            self.instrument(builder.name),
+        )))
+    }
+
+    fn u64_gauge(&self, builder: InstrumentBuilder<'_, Gauge<u64>>) -> Gauge<u64> {
+        Gauge::new(Arc::new(GaugeInstrument(self.instrument(builder.name))))
+    }
+
+    fn f64_gauge(&self, builder: InstrumentBuilder<'_, Gauge<f64>>) -> Gauge<f64> {
+        Gauge::new(Arc::new(GaugeInstrument(self.instrument(builder.name))))
+    }
+
+    fn i64_gauge(&self, builder: InstrumentBuilder<'_, Gauge<i64>>) -> Gauge<i64> {
+        Gauge::new(Arc::new(GaugeInstrument(self.instrument(builder.name))))
+    }
+
+    fn f64_histogram(&self, builder: HistogramBuilder<'_, Histogram<f64>>) -> Histogram<f64> {
+        Histogram::new(Arc::new(HistogramInstrument {
+            nanos_per_unit: builder.unit.as_deref().and_then(nanos_per_unit),
+            instrument: self.instrument(builder.name),
+        }))
+    }
+
+    fn u64_histogram(&self, builder: HistogramBuilder<'_, Histogram<u64>>) -> Histogram<u64> {
+        Histogram::new(Arc::new(HistogramInstrument {
+            nanos_per_unit: builder.unit.as_deref().and_then(nanos_per_unit),
+            instrument: self.instrument(builder.name),
+        }))
+    }
+}
+
+fn nanos_per_unit(unit: &str) -> Option<f64> {
+    match unit {
+        "s" => Some(1e9),
+        "ms" => Some(1e6),
+        "us" => Some(1e3),
+        "ns" => Some(1.),
+        _ => None,
+    }
+}
+
+struct Instrument {
+    registry: Arc<MetricRegistry>,
+    name: Cow<'static, str>,
+    conflict_warned: AtomicBool,
+}
+
+impl Instrument {
+    fn id(&self, attributes: &[KeyValue]) -> MetricId {
+        attributes
+            .iter()
+            .fold(MetricId::new(self.name.clone()), |id, attribute| {
+                id.with_tag(
+                    attribute.key.as_str().to_string(),
+                    attribute.value.as_str().into_owned(),
+                )
+            })
+    }
+
+    // Returns `None` rather than panicking if a metric of another type is registered with the ID.
+    fn metric<F, G, T>(&self, attributes: &[KeyValue], make_metric: F, cast: G) -> Option<T>
+    where
+        F: FnOnce() -> Metric,
+        G: FnOnce(Metric) -> Option<T>,
+    {
+        let (id, metric) = self.registry.metric_with(self.id(attributes), make_metric);
+        let metric = cast(metric);
+        if metric.is_none() && !self.conflict_warned.swap(true, Ordering::Relaxed) {
+            witchcraft_log::warn!(
+                "metric already registered with a different type, dropping OpenTelemetry measurements",
+                safe: { metricName: id.name() },
+            );
+        }
+        metric
+    }
+
+    fn counter(&self, attributes: &[KeyValue]) -> Option<Arc<RegistryCounter>> {
+        self.metric(
+            attributes,
+            || Metric::Counter(Arc::default()),
+            |metric| match metric {
+                Metric::Counter(counter) => Some(counter),
+                _ => None,
+            },
+        )
+    }
+
+    fn f64_counter(&self, attributes: &[KeyValue]) -> Option<Arc<F64Counter>> {
+        self.metric(
+            attributes,
+            || Metric::Gauge(Arc::new(F64Counter::new())),
+            |metric| match metric {
+                Metric::Gauge(gauge) => gauge.downcast_arc().ok(),
+                _ => None,
+            },
+        )
+    }
+
+    fn f64_gauge(&self, attributes: &[KeyValue]) -> Option<Arc<F64Gauge>> {
+        self.metric(
+            attributes,
+            || Metric::Gauge(Arc::new(F64Gauge::new())),
+            |metric| match metric {
+                Metric::Gauge(gauge) => gauge.downcast_arc().ok(),
+                _ => None,
+            },
+        )
+    }
+
+    fn histogram(&self, attributes: &[KeyValue]) -> Option<Arc<RegistryHistogram>> {
+        let clock = self.registry.clock();
+        self.metric(
+            attributes,
+            || {
+                Metric::Histogram(Arc::new(RegistryHistogram::new(
+                    ExponentiallyDecayingReservoir::new_with(clock.clone()),
+                )))
+            },
+            |metric| match metric {
+                Metric::Histogram(histogram) => Some(histogram),
+                _ => None,
+            },
+        )
+    }
+
+    fn timer(&self, attributes: &[KeyValue]) -> Option<Arc<Timer>> {
+        let clock = self.registry.clock();
+        self.metric(
+            attributes,
+            || {
+                Metric::Timer(Arc::new(Timer::new_with(
+                    ExponentiallyDecayingReservoir::new_with(clock.clone()),
+                    clock.clone(),
+                )))
+            },
+            |metric| match metric {
+                Metric::Timer(timer) => Some(timer),
+                _ => None,
+            },
+        )
+    }
+}
+
+// Integer counters and up-down counters use the instrument directly.
+
+impl SyncInstrument<u64> for Instrument {
+    fn measure(&self, measurement: u64, attributes: &[KeyValue]) {
+        if let Some(counter) = self.counter(attributes) {
+            counter.inc_by(measurement);
+        }
+    }
+}
+
+impl SyncInstrument<i64> for Instrument {
+    fn measure(&self, measurement: i64, attributes: &[KeyValue]) {
+        if let Some(counter) = self.counter(attributes) {
+            counter.add(measurement);
+        }
+    }
+}
+
+struct F64CounterInstrument(Arc<Instrument>);
+
+impl SyncInstrument<f64> for F64CounterInstrument {
+    fn measure(&self, measurement: f64, attributes: &[KeyValue]) {
+        if let Some(counter) = self.0.f64_counter(attributes) {
+            counter.add(measurement);
+        }
+    }
+}
+
+struct GaugeInstrument(Arc<Instrument>);
+
+impl GaugeInstrument {
+    fn set(&self, value: f64, attributes: &[KeyValue]) {
+        if let Some(gauge) = self.0.f64_gauge(attributes) {
+            gauge.set(value);
+        }
+    }
+}
+
+impl SyncInstrument<u64> for GaugeInstrument {
+    fn measure(&self, measurement: u64, attributes: &[KeyValue]) {
+        self.set(measurement as f64, attributes);
+    }
+}
+
+impl SyncInstrument<i64> for GaugeInstrument {
+    fn measure(&self, measurement: i64, attributes: &[KeyValue]) {
+        self.set(measurement as f64, attributes);
+    }
+}
+
+impl SyncInstrument<f64> for GaugeInstrument {
+    fn measure(&self, measurement: f64, attributes: &[KeyValue]) {
+        self.set(measurement, attributes);
+    }
+}
+
+struct HistogramInstrument {
+    instrument: Arc<Instrument>,
+    // histograms with a time unit are recorded as timers
+    nanos_per_unit: Option<f64>,
+}
+
+impl HistogramInstrument {
+    fn record(&self, measurement: f64, attributes: &[KeyValue]) {
+        match self.nanos_per_unit {
+            Some(nanos_per_unit) => {
+                if let Some(duration) = f64_duration(measurement, nanos_per_unit) {
+                    if let Some(timer) = self.instrument.timer(attributes) {
+                        timer.update(duration);
+                    }
+                }
+            }
+            None => {
+                if let Some(histogram) = self.instrument.histogram(attributes) {
+                    histogram.update(measurement.round() as i64);
+                }
+            }
+        }
+    }
+}
+
+impl SyncInstrument<u64> for HistogramInstrument {
+    fn measure(&self, measurement: u64, attributes: &[KeyValue]) {
+        self.record(measurement as f64, attributes);
+    }
+}
+
+impl SyncInstrument<f64> for HistogramInstrument {
+    fn measure(&self, measurement: f64, attributes: &[KeyValue]) {
+        self.record(measurement, attributes);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_value::Value;
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn basic() {
+        let registry = Arc::new(MetricRegistry::new());
+        let meter = RegistryMeterProvider::new(registry.clone()).meter("test");
+
+        let counter = meter.u64_counter("counter").build();
+        counter.add(2, &[KeyValue::new("tag", "value")]);
+        counter.add(3, &[KeyValue::new("tag", "value")]);
+        counter.add(1, &[KeyValue::new("tag", "other")]);
+
+        let up_down = meter.i64_up_down_counter("upDown").build();
+        up_down.add(2, &[]);
+        up_down.add(-5, &[]);
+
+        meter.f64_gauge("gauge").build().record(1.5, &[]);
+
+        meter.f64_histogram("histogram").build().record(2.6, &[]);
+
+        let f64_counter = meter.f64_counter("f64Counter").build();
+        for _ in 0..10 {
+            f64_counter.add(0.5, &[]);
+        }
+
+        let f64_up_down = meter.f64_up_down_counter("f64UpDown").build();
+        f64_up_down.add(0.5, &[]);
+        f64_up_down.add(-0.75, &[]);
+
+        let timer = meter.f64_histogram("timer").with_unit("s").build();
+        timer.record(0.12, &[]);
+        timer.record(0.45, &[]);
+        timer.record(-1., &[]);
+
+        let ms_timer = meter.u64_histogram("msTimer").with_unit("ms").build();
+        ms_timer.record(3, &[]);
+
+        let id = MetricId::new("counter").with_tag("tag", "value");
+        assert_eq!(registry.counter(id).count(), 5);
+        let id = MetricId::new("counter").with_tag("tag", "other");
+        assert_eq!(registry.counter(id).count(), 1);
+
+        assert_eq!(registry.counter("upDown").count(), -3);
+
+        match registry
+            .metrics()
+            .iter()
+            .find(|(id, _)| id.name() == "gauge")
+        {
+            Some((_, Metric::Gauge(gauge))) => assert_eq!(gauge.value(), Value::F64(1.5)),
+            _ => panic!("gauge not registered"),
+        }
+
+        let histogram = registry.histogram("histogram");
+        assert_eq!(histogram.count(), 1);
+        assert_eq!(histogram.snapshot().max(), 3);
+
+        assert_eq!(registry.f64_counter("f64Counter").count(), 5.);
+        assert_eq!(registry.f64_counter("f64UpDown").count(), -0.25);
+
+        let timer = registry.timer("timer");
+        assert_eq!(timer.count(), 2);
+        assert_eq!(timer.snapshot().min(), 120_000_000);
+        assert_eq!(timer.snapshot().max(), 450_000_000);
+
+        assert_eq!(registry.timer("msTimer").snapshot().max(), 3_000_000);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn type_conflicts() {
+        let registry = Arc::new(MetricRegistry::new());
+        let meter = RegistryMeterProvider::new(registry.clone()).meter("test");
+
+        registry.gauge("counter", || 1);
+        registry.counter("f64Counter").inc();
+        registry.counter("gauge").inc();
+        registry.f64_gauge("histogram").set(1.);
+        registry.histogram("timer").update(1);
+
+        meter.u64_counter("counter").build().add(1, &[]);
+        meter.i64_up_down_counter("counter").build().add(1, &[]);
+        meter.f64_counter("f64Counter").build().add(1., &[]);
+        meter.f64_gauge("gauge").build().record(2., &[]);
+        meter.f64_histogram("histogram").build().record(2., &[]);
+        let timer = meter.f64_histogram("timer").with_unit("s").build();
+        timer.record(2., &[]);
+        timer.record(3., &[]);
+
+        assert_eq!(registry.gauge("counter", || 0).value(), Value::I32(1));
+        assert_eq!(registry.counter("f64Counter").count(), 1);
+        assert_eq!(registry.counter("gauge").count(), 1);
+        assert_eq!(registry.f64_gauge("histogram").get(), 1.);
+        assert_eq!(registry.histogram("timer").count(), 1);
+    }
+}
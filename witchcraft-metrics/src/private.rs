@@ -0,0 +1,156 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{global, global_registry_set, BuildInfo, Metric, MetricId, MetricRegistry, Snapshot};
+use once_cell::sync::OnceCell;
+use serde_value::Value;
+
+pub fn build_info(
+    name: &'static str,
+    version: &'static str,
+    git_sha: Option<&'static str>,
+    build_time: Option<&'static str>,
+) -> BuildInfo {
+    let mut info = BuildInfo::new(name, version);
+    if let Some(git_sha) = git_sha {
+        info = info.with_git_sha(git_sha);
+    }
+    if let Some(build_time) = build_time {
+        info = info.with_build_time(build_time);
+    }
+    info
+}
+
+pub struct CallSite<T>(OnceCell<T>);
+
+impl<T> CallSite<T>
+where
+    T: Clone,
+{
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> CallSite<T> {
+        CallSite(OnceCell::new())
+    }
+
+    pub fn get<F>(&self, register: F) -> T
+    where
+        F: FnOnce(&MetricRegistry) -> T,
+    {
+        if let Some(metric) = self.0.get() {
+            return metric.clone();
+        }
+
+        // metrics from the fallback registry can't be cached since they'd be stuck there after the global registry is
+        // set
+        let set = global_registry_set();
+        let metric = register(global());
+        if set {
+            let _ = self.0.set(metric.clone());
+        }
+        metric
+    }
+}
+
+pub struct MetricAssert {
+    id: MetricId,
+    metric: Metric,
+}
+
+pub fn metric_assert<T>(registry: &MetricRegistry, id: T) -> MetricAssert
+where
+    T: Into<MetricId>,
+{
+    let id = id.into();
+    match registry.get(id.clone()) {
+        Some(metric) => MetricAssert { id, metric },
+        None => panic!("metric {:?} is not registered", id),
+    }
+}
+
+impl MetricAssert {
+    pub fn id(&self) -> &MetricId {
+        &self.id
+    }
+
+    pub fn count(&self) -> i64 {
+        match &self.metric {
+            Metric::Counter(counter) => counter.count(),
+            Metric::Meter(meter) => meter.count(),
+            Metric::Histogram(histogram) => histogram.count() as i64,
+            Metric::Timer(timer) => timer.count(),
+            Metric::Gauge(_) => panic!("gauge {:?} has no count", self.id),
+        }
+    }
+
+    pub fn value(&self) -> Value {
+        match &self.metric {
+            Metric::Gauge(gauge) => gauge.value(),
+            _ => panic!("metric {:?} is not a gauge", self.id),
+        }
+    }
+
+    pub fn min(&self) -> i64 {
+        self.snapshot().min()
+    }
+
+    pub fn max(&self) -> i64 {
+        self.snapshot().max()
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.snapshot().mean()
+    }
+
+    fn snapshot(&self) -> Box<dyn Snapshot> {
+        match &self.metric {
+            Metric::Histogram(histogram) => histogram.snapshot(),
+            Metric::Timer(timer) => timer.snapshot(),
+            _ => panic!("metric {:?} is not a histogram or timer", self.id),
+        }
+    }
+}
+
+pub const fn valid_metric_name(name: &str) -> bool {
+    let name = name.as_bytes();
+    let mut segment_start = true;
+
+    let mut i = 0;
+    while i < name.len() {
+        match name[i] {
+            b'.' if !segment_start => segment_start = true,
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' => segment_start = false,
+            _ => return false,
+        }
+        i += 1;
+    }
+
+    !segment_start
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn metric_names() {
+        assert!(valid_metric_name("server"));
+        assert!(valid_metric_name("server.response-size.p_99"));
+
+        assert!(!valid_metric_name(""));
+        assert!(!valid_metric_name(".server"));
+        assert!(!valid_metric_name("server."));
+        assert!(!valid_metric_name("server..response"));
+        assert!(!valid_metric_name("server response"));
+    }
+}
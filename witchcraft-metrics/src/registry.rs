@@ -12,9 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use crate::{
-    Clock, Counter, ExponentiallyDecayingReservoir, Gauge, Histogram, Meter, MetricId, Timer,
+    Clock, Counter, ExponentiallyDecayingReservoir, F64Counter, F64Gauge, Gauge, Histogram, Meter,
+    Metered, MetricId, Timer,
 };
 use parking_lot::Mutex;
+use serde_value::Value;
+use std::borrow::Cow;
 use std::collections::hash_map::Entry;
 use std::collections::{hash_map, HashMap};
 use std::sync::Arc;
@@ -25,7 +28,7 @@ pub enum Metric {
     /// A counter metric.
     Counter(Arc<Counter>),
     /// A meter metric.
-    Meter(Arc<Meter>),
+    Meter(Arc<dyn Metered>),
     /// A gauge metric.
     Gauge(Arc<dyn Gauge>),
     /// A histogram metric.
@@ -34,6 +37,24 @@ pub enum Metric {
     Timer(Arc<Timer>),
 }
 
+/// A hook used by a [`MetricRegistry`] to normalize the tag values of metric IDs as metrics are registered.
+///
+/// It is implemented for all closures with the signature of [`TagNormalizer::normalize`].
+pub trait TagNormalizer: 'static + Sync + Send {
+    /// Returns the normalized form of the value of the tag `key` on a metric named `name`, or `None` if the value should
+    /// be left unchanged.
+    fn normalize(&self, name: &str, key: &str, value: &str) -> Option<Cow<'static, str>>;
+}
+
+impl<F> TagNormalizer for F
+where
+    F: Fn(&str, &str, &str) -> Option<Cow<'static, str>> + 'static + Sync + Send,
+{
+    fn normalize(&self, name: &str, key: &str, value: &str) -> Option<Cow<'static, str>> {
+        self(name, key, value)
+    }
+}
+
 /// A collection of metrics.
 ///
 /// Many of the registry's methods take a `T: Into<MetricId>` rather than just a [`MetricId`]. This allows you to pass
@@ -48,15 +69,80 @@ pub enum Metric {
 /// let yak_shavings = registry.counter(MetricId::new("shavings").with_tag("animal", "yak"));
 /// ```
 pub struct MetricRegistry {
-    metrics: Mutex<Arc<HashMap<Arc<MetricId>, Metric>>>,
+    state: Mutex<State>,
     clock: Arc<dyn Clock>,
+    tag_normalizer: Option<Arc<dyn TagNormalizer>>,
+}
+
+struct State {
+    metrics: Arc<HashMap<Arc<MetricId>, Metric>>,
+    cardinality_limits: HashMap<Cow<'static, str>, CardinalityLimit>,
+}
+
+struct CardinalityLimit {
+    max: usize,
+    current: usize,
+    warned: bool,
+}
+
+impl State {
+    // Returns the ID to register the metric under, along with the limit to warn about if it was just exceeded.
+    fn resolve_id(&mut self, id: MetricId) -> (MetricId, Option<usize>) {
+        let limit = match self.cardinality_limits.get_mut(id.name()) {
+            Some(limit) => limit,
+            None => return (id, None),
+        };
+
+        if limit.current < limit.max || self.metrics.contains_key(&id) {
+            return (id, None);
+        }
+
+        let collapsed = id.tags().iter().fold(
+            MetricId::new(id.name().to_string()),
+            |collapsed, (key, _)| collapsed.with_tag(key.to_string(), "other"),
+        );
+
+        let warn = if limit.warned {
+            None
+        } else {
+            limit.warned = true;
+            Some(limit.max)
+        };
+
+        (collapsed, warn)
+    }
+
+    fn record_insert(&mut self, id: &MetricId) {
+        if let Some(limit) = self.cardinality_limits.get_mut(id.name()) {
+            limit.current += 1;
+        }
+    }
+
+    fn record_remove(&mut self, id: &MetricId) {
+        if let Some(limit) = self.cardinality_limits.get_mut(id.name()) {
+            limit.current -= 1;
+        }
+    }
+}
+
+fn warn_cardinality_exceeded(name: &str, limit: Option<usize>) {
+    if let Some(limit) = limit {
+        witchcraft_log::warn!(
+            "metric tag cardinality limit exceeded, collapsing new tag values into `other`",
+            safe: { metricName: name, limit: limit },
+        );
+    }
 }
 
 impl Default for MetricRegistry {
     fn default() -> Self {
         MetricRegistry {
-            metrics: Mutex::new(Arc::new(HashMap::new())),
+            state: Mutex::new(State {
+                metrics: Arc::new(HashMap::new()),
+                cardinality_limits: HashMap::new(),
+            }),
             clock: crate::SYSTEM_CLOCK.clone(),
+            tag_normalizer: None,
         }
     }
 }
@@ -82,6 +168,93 @@ impl MetricRegistry {
         &self.clock
     }
 
+    /// Sets a [`TagNormalizer`] applied to the IDs passed to the registry's methods.
+    ///
+    /// This can be used to ensure consistent tag values across call sites, for example by lowercasing values or mapping
+    /// raw request paths to their route templates. The normalizer only applies to metrics registered after it is set.
+    ///
+    /// Defaults to `None`.
+    #[inline]
+    pub fn set_tag_normalizer(&mut self, tag_normalizer: Arc<dyn TagNormalizer>) {
+        self.tag_normalizer = Some(tag_normalizer);
+    }
+
+    fn normalize_id(&self, id: MetricId) -> MetricId {
+        let tag_normalizer = match &self.tag_normalizer {
+            Some(tag_normalizer) => tag_normalizer,
+            None => return id,
+        };
+
+        let normalized = id
+            .tags()
+            .iter()
+            .filter_map(|(key, value)| {
+                tag_normalizer
+                    .normalize(id.name(), key, value)
+                    .map(|value| (key.to_string(), value))
+            })
+            .collect::<Vec<_>>();
+
+        normalized
+            .into_iter()
+            .fold(id, |id, (key, value)| id.with_tag(key, value))
+    }
+
+    /// Limits the number of distinct tag sets registered for metrics with the specified name.
+    ///
+    /// Once the limit is reached, metrics registered with new tag sets are instead registered with all of their tag
+    /// values replaced by `other`, and a warning is logged the first time this happens. At most `limit + 1` metrics will
+    /// be registered with the name. This guards against unbounded memory use from tags with unbounded cardinality
+    /// such as user IDs.
+    pub fn set_cardinality_limit<T>(&mut self, name: T, limit: usize)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let name = name.into();
+        let state = self.state.get_mut();
+        let current = state.metrics.keys().filter(|id| id.name() == name).count();
+        state.cardinality_limits.insert(
+            name,
+            CardinalityLimit {
+                max: limit,
+                current,
+                warned: false,
+            },
+        );
+    }
+
+    pub(crate) fn metric_with<F>(&self, id: MetricId, make_metric: F) -> (Arc<MetricId>, Metric)
+    where
+        F: FnOnce() -> Metric,
+    {
+        let id = self.normalize_id(id);
+        let mut state = self.state.lock();
+        let (id, exceeded) = state.resolve_id(id);
+
+        // look the metric up before calling make_mut, which would copy the map if a snapshot of it is still alive
+        if let Some((id, metric)) = state.metrics.get_key_value(&id) {
+            let (id, metric) = (id.clone(), metric.clone());
+            drop(state);
+            warn_cardinality_exceeded(id.name(), exceeded);
+            return (id, metric);
+        }
+
+        let (id, metric, inserted) = match Arc::make_mut(&mut state.metrics).entry(Arc::new(id)) {
+            Entry::Occupied(e) => (e.key().clone(), e.get().clone(), false),
+            Entry::Vacant(e) => {
+                let id = e.key().clone();
+                (id, e.insert(make_metric()).clone(), true)
+            }
+        };
+        if inserted {
+            state.record_insert(&id);
+        }
+        drop(state);
+
+        warn_cardinality_exceeded(id.name(), exceeded);
+        (id, metric)
+    }
+
     /// Returns the counter with the specified ID, using make_counter to create it if absent.
     ///
     /// # Panics
@@ -92,16 +265,9 @@ impl MetricRegistry {
         T: Into<MetricId>,
         F: FnOnce() -> Counter,
     {
-        match Arc::make_mut(&mut self.metrics.lock()).entry(Arc::new(id.into())) {
-            Entry::Occupied(e) => match e.get() {
-                Metric::Counter(c) => c.clone(),
-                _ => panic!("metric already registered as a non-counter: {:?}", e.key()),
-            },
-            Entry::Vacant(e) => {
-                let counter = Arc::new(make_counter());
-                e.insert(Metric::Counter(counter.clone()));
-                counter
-            }
+        match self.metric_with(id.into(), || Metric::Counter(Arc::new(make_counter()))) {
+            (_, Metric::Counter(counter)) => counter,
+            (id, _) => panic!("metric already registered as a non-counter: {:?}", id),
         }
     }
 
@@ -122,21 +288,15 @@ impl MetricRegistry {
     /// # Panics
     ///
     /// Panics if a metric is registered with the ID that is not a meter.
-    pub fn meter_with<T, F>(&self, id: T, make_meter: F) -> Arc<Meter>
+    pub fn meter_with<T, F, M>(&self, id: T, make_meter: F) -> Arc<dyn Metered>
     where
         T: Into<MetricId>,
-        F: FnOnce() -> Meter,
+        F: FnOnce() -> M,
+        M: Metered,
     {
-        match Arc::make_mut(&mut self.metrics.lock()).entry(Arc::new(id.into())) {
-            Entry::Occupied(e) => match e.get() {
-                Metric::Meter(m) => m.clone(),
-                _ => panic!("metric already registered as a non-meter: {:?}", e.key()),
-            },
-            Entry::Vacant(e) => {
-                let meter = Arc::new(make_meter());
-                e.insert(Metric::Meter(meter.clone()));
-                meter
-            }
+        match self.metric_with(id.into(), || Metric::Meter(Arc::new(make_meter()))) {
+            (_, Metric::Meter(meter)) => meter,
+            (id, _) => panic!("metric already registered as a non-meter: {:?}", id),
         }
     }
 
@@ -145,7 +305,7 @@ impl MetricRegistry {
     /// # Panics
     ///
     /// Panics if a metric is registered with the ID that is not a meter.
-    pub fn meter<T>(&self, id: T) -> Arc<Meter>
+    pub fn meter<T>(&self, id: T) -> Arc<dyn Metered>
     where
         T: Into<MetricId>,
     {
@@ -163,16 +323,9 @@ impl MetricRegistry {
         F: FnOnce() -> G,
         G: Gauge,
     {
-        match Arc::make_mut(&mut self.metrics.lock()).entry(Arc::new(id.into())) {
-            Entry::Occupied(e) => match e.get() {
-                Metric::Gauge(m) => m.clone(),
-                _ => panic!("metric already registered as a non-gauge: {:?}", e.key()),
-            },
-            Entry::Vacant(e) => {
-                let gauge = Arc::new(make_gauge());
-                e.insert(Metric::Gauge(gauge.clone()));
-                gauge
-            }
+        match self.metric_with(id.into(), || Metric::Gauge(Arc::new(make_gauge()))) {
+            (_, Metric::Gauge(gauge)) => gauge,
+            (id, _) => panic!("metric already registered as a non-gauge: {:?}", id),
         }
     }
 
@@ -189,14 +342,77 @@ impl MetricRegistry {
         self.gauge_with(id, || gauge)
     }
 
+    /// Returns the gauge of type `G` with the specified ID, using make_gauge to register a new one if absent.
+    ///
+    /// Returns `None` if a metric is registered with the ID that is not a `G`.
+    pub(crate) fn typed_gauge_with<T, F, G>(&self, id: T, make_gauge: F) -> Option<Arc<G>>
+    where
+        T: Into<MetricId>,
+        F: FnOnce() -> G,
+        G: Gauge,
+    {
+        match self.metric_with(id.into(), || Metric::Gauge(Arc::new(make_gauge()))) {
+            (_, Metric::Gauge(gauge)) => gauge.downcast_arc().ok(),
+            _ => None,
+        }
+    }
+
     /// Adds a gauge to the registry, overwriting the previous metric with that ID if present.
     pub fn replace_gauge<T, G>(&self, id: T, gauge: G)
     where
         T: Into<MetricId>,
         G: Gauge,
     {
-        Arc::make_mut(&mut self.metrics.lock())
-            .insert(Arc::new(id.into()), Metric::Gauge(Arc::new(gauge)));
+        let id = self.normalize_id(id.into());
+        let mut state = self.state.lock();
+        let (id, exceeded) = state.resolve_id(id);
+
+        let id = Arc::new(id);
+        if Arc::make_mut(&mut state.metrics)
+            .insert(id.clone(), Metric::Gauge(Arc::new(gauge)))
+            .is_none()
+        {
+            state.record_insert(&id);
+        }
+        drop(state);
+
+        warn_cardinality_exceeded(id.name(), exceeded);
+    }
+
+    /// Returns the floating point counter with the specified ID, registering a new one if absent.
+    ///
+    /// The counter is registered as a gauge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a metric is registered with the ID that is not an `F64Counter` gauge.
+    pub fn f64_counter<T>(&self, id: T) -> Arc<F64Counter>
+    where
+        T: Into<MetricId>,
+    {
+        match self.metric_with(id.into(), || Metric::Gauge(Arc::new(F64Counter::new()))) {
+            (id, Metric::Gauge(gauge)) => gauge.downcast_arc().unwrap_or_else(|_| {
+                panic!("gauge already registered as a non-F64Counter: {:?}", id)
+            }),
+            (id, _) => panic!("metric already registered as a non-gauge: {:?}", id),
+        }
+    }
+
+    /// Returns the floating point gauge with the specified ID, registering a new one if absent.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a metric is registered with the ID that is not an `F64Gauge`.
+    pub fn f64_gauge<T>(&self, id: T) -> Arc<F64Gauge>
+    where
+        T: Into<MetricId>,
+    {
+        match self.metric_with(id.into(), || Metric::Gauge(Arc::new(F64Gauge::new()))) {
+            (id, Metric::Gauge(gauge)) => gauge
+                .downcast_arc()
+                .unwrap_or_else(|_| panic!("gauge already registered as a non-F64Gauge: {:?}", id)),
+            (id, _) => panic!("metric already registered as a non-gauge: {:?}", id),
+        }
     }
 
     /// Returns the histogram with the specified ID, using make_histogram to create it if absent.
@@ -209,19 +425,9 @@ impl MetricRegistry {
         T: Into<MetricId>,
         F: FnOnce() -> Histogram,
     {
-        match Arc::make_mut(&mut self.metrics.lock()).entry(Arc::new(id.into())) {
-            Entry::Occupied(e) => match e.get() {
-                Metric::Histogram(m) => m.clone(),
-                _ => panic!(
-                    "metric already registered as a non-histogram: {:?}",
-                    e.key()
-                ),
-            },
-            Entry::Vacant(e) => {
-                let histogram = Arc::new(make_histogram());
-                e.insert(Metric::Histogram(histogram.clone()));
-                histogram
-            }
+        match self.metric_with(id.into(), || Metric::Histogram(Arc::new(make_histogram()))) {
+            (_, Metric::Histogram(histogram)) => histogram,
+            (id, _) => panic!("metric already registered as a non-histogram: {:?}", id),
         }
     }
 
@@ -249,16 +455,9 @@ impl MetricRegistry {
         T: Into<MetricId>,
         F: FnOnce() -> Timer,
     {
-        match Arc::make_mut(&mut self.metrics.lock()).entry(Arc::new(id.into())) {
-            Entry::Occupied(e) => match e.get() {
-                Metric::Timer(m) => m.clone(),
-                _ => panic!("metric already registered as a non-timer: {:?}", e.key()),
-            },
-            Entry::Vacant(e) => {
-                let timer = Arc::new(make_timer());
-                e.insert(Metric::Timer(timer.clone()));
-                timer
-            }
+        match self.metric_with(id.into(), || Metric::Timer(Arc::new(make_timer()))) {
+            (_, Metric::Timer(timer)) => timer,
+            (id, _) => panic!("metric already registered as a non-timer: {:?}", id),
         }
     }
 
@@ -279,29 +478,289 @@ impl MetricRegistry {
         })
     }
 
+    /// Returns the timer with the specified ID, creating a default instance if absent, along with gauges derived from it.
+    ///
+    /// This is intended for use with metrics backends which only support plain gauges. The following gauges are
+    /// registered with the timer's tags, each computed from a snapshot of the timer when read:
+    ///
+    /// * `<name>.p50`, `<name>.p95` and `<name>.p99` - the quantiles of the timer's durations in nanoseconds.
+    /// * `<name>.max` - the maximum of the timer's durations in nanoseconds.
+    /// * `<name>.mean` - the mean of the timer's durations in nanoseconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a metric is registered with the ID or the ID of one of the derived gauges that is not of the
+    /// corresponding type.
+    pub fn timer_with_gauges<T>(&self, id: T) -> Arc<Timer>
+    where
+        T: Into<MetricId>,
+    {
+        let id = id.into();
+        let timer = self.timer(id.clone());
+
+        for &(suffix, quantile) in &[("p50", 0.5), ("p95", 0.95), ("p99", 0.99)] {
+            let timer = timer.clone();
+            self.gauge(id.with_name_suffix(suffix), move || {
+                timer.snapshot().value(quantile)
+            });
+        }
+        self.gauge(id.with_name_suffix("max"), {
+            let timer = timer.clone();
+            move || timer.snapshot().max()
+        });
+        self.gauge(id.with_name_suffix("mean"), {
+            let timer = timer.clone();
+            move || timer.snapshot().mean()
+        });
+
+        timer
+    }
+
+    /// Returns the metric registered with the specified ID, if present.
+    ///
+    /// Unlike the typed accessors such as [`MetricRegistry::counter`], this never registers a new metric.
+    pub fn get<T>(&self, id: T) -> Option<Metric>
+    where
+        T: Into<MetricId>,
+    {
+        let id = self.normalize_id(id.into());
+        self.state.lock().metrics.get(&id).cloned()
+    }
+
     /// Removes a metric from the registry, returning it if present.
     pub fn remove<T>(&self, id: T) -> Option<Metric>
     where
         T: Into<MetricId>,
     {
-        Arc::make_mut(&mut self.metrics.lock()).remove(&id.into())
+        let id = self.normalize_id(id.into());
+        let mut state = self.state.lock();
+        if !state.metrics.contains_key(&id) {
+            return None;
+        }
+
+        let metric = Arc::make_mut(&mut state.metrics).remove(&id);
+        state.record_remove(&id);
+        metric
     }
 
     /// Returns a snapshot of the metrics in the registry.
     ///
     /// Modifications to the registry after this method is called will not affect the state of the returned `Metrics`.
+    /// The count of each counter, meter, histogram, and timer and the value of each gauge are recorded when the
+    /// snapshot is taken so that [`Metrics::diff`] can report value changes.
     pub fn metrics(&self) -> Metrics {
-        Metrics(self.metrics.lock().clone())
+        let metrics = self.state.lock().metrics.clone();
+        let values = metrics
+            .iter()
+            .map(|(id, metric)| (id.clone(), MetricValue::new(metric)))
+            .collect();
+
+        Metrics { metrics, values }
+    }
+
+    /// Returns statistics about the metrics currently in the registry.
+    ///
+    /// This is intended to help diagnose unexpected growth in the number of registered metrics, for example due to a
+    /// tag with unbounded cardinality.
+    pub fn stats(&self) -> RegistryStats {
+        let mut stats = RegistryStats {
+            counters: 0,
+            meters: 0,
+            gauges: 0,
+            histograms: 0,
+            timers: 0,
+            cardinalities: vec![],
+        };
+
+        let mut cardinalities = HashMap::new();
+        for (id, metric) in &self.metrics() {
+            match metric {
+                Metric::Counter(_) => stats.counters += 1,
+                Metric::Meter(_) => stats.meters += 1,
+                Metric::Gauge(_) => stats.gauges += 1,
+                Metric::Histogram(_) => stats.histograms += 1,
+                Metric::Timer(_) => stats.timers += 1,
+            }
+            *cardinalities.entry(id.name().to_string()).or_insert(0) += 1;
+        }
+
+        stats.cardinalities = cardinalities.into_iter().collect();
+        stats
+            .cardinalities
+            .sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        stats
+    }
+}
+
+/// Statistics about the metrics in a registry.
+pub struct RegistryStats {
+    counters: usize,
+    meters: usize,
+    gauges: usize,
+    histograms: usize,
+    timers: usize,
+    cardinalities: Vec<(String, usize)>,
+}
+
+impl RegistryStats {
+    /// Returns the total number of metrics.
+    #[inline]
+    pub fn metrics(&self) -> usize {
+        self.counters + self.meters + self.gauges + self.histograms + self.timers
+    }
+
+    /// Returns the number of counters.
+    #[inline]
+    pub fn counters(&self) -> usize {
+        self.counters
+    }
+
+    /// Returns the number of meters.
+    #[inline]
+    pub fn meters(&self) -> usize {
+        self.meters
+    }
+
+    /// Returns the number of gauges.
+    #[inline]
+    pub fn gauges(&self) -> usize {
+        self.gauges
+    }
+
+    /// Returns the number of histograms.
+    #[inline]
+    pub fn histograms(&self) -> usize {
+        self.histograms
+    }
+
+    /// Returns the number of timers.
+    #[inline]
+    pub fn timers(&self) -> usize {
+        self.timers
+    }
+
+    /// Returns the number of distinct metric names.
+    #[inline]
+    pub fn names(&self) -> usize {
+        self.cardinalities.len()
+    }
+
+    /// Returns up to `n` metric names with the largest number of distinct tag sets, along with that number.
+    ///
+    /// Names are sorted by decreasing cardinality.
+    #[inline]
+    pub fn highest_cardinality(&self, n: usize) -> &[(String, usize)] {
+        &self.cardinalities[..n.min(self.cardinalities.len())]
     }
 }
 
 /// A snapshot of the metrics in a registry.
-pub struct Metrics(Arc<HashMap<Arc<MetricId>, Metric>>);
+pub struct Metrics {
+    metrics: Arc<HashMap<Arc<MetricId>, Metric>>,
+    values: HashMap<Arc<MetricId>, MetricValue>,
+}
 
 impl Metrics {
     /// Returns an iterator over the metrics.
     pub fn iter(&self) -> MetricsIter<'_> {
-        MetricsIter(self.0.iter())
+        MetricsIter(self.metrics.iter())
+    }
+
+    /// Returns the differences between this snapshot and an earlier one.
+    ///
+    /// A metric is considered changed if the instance registered under its ID was replaced (e.g. via
+    /// [`MetricRegistry::replace_gauge`]) or if its count or value differs between the snapshots.
+    pub fn diff<'a>(&'a self, previous: &'a Metrics) -> MetricsDiff<'a> {
+        let mut diff = MetricsDiff {
+            added: vec![],
+            removed: vec![],
+            changed: vec![],
+        };
+
+        for (id, metric) in &*self.metrics {
+            match previous.metrics.get(id) {
+                Some(old)
+                    if same_metric(old, metric)
+                        && self.values.get(id) == previous.values.get(id) => {}
+                Some(_) => diff.changed.push((&**id, metric)),
+                None => diff.added.push((&**id, metric)),
+            }
+        }
+
+        for (id, metric) in &*previous.metrics {
+            if !self.metrics.contains_key(id) {
+                diff.removed.push((&**id, metric));
+            }
+        }
+
+        diff
+    }
+}
+
+#[derive(PartialEq)]
+enum MetricValue {
+    Count(i64),
+    Gauge(Value),
+}
+
+impl MetricValue {
+    fn new(metric: &Metric) -> MetricValue {
+        match metric {
+            Metric::Counter(counter) => MetricValue::Count(counter.count()),
+            Metric::Meter(meter) => MetricValue::Count(meter.count()),
+            Metric::Gauge(gauge) => MetricValue::Gauge(gauge.value()),
+            Metric::Histogram(histogram) => MetricValue::Count(histogram.count() as i64),
+            Metric::Timer(timer) => MetricValue::Count(timer.count()),
+        }
+    }
+}
+
+fn same_metric(a: &Metric, b: &Metric) -> bool {
+    match (a, b) {
+        (Metric::Counter(a), Metric::Counter(b)) => Arc::ptr_eq(a, b),
+        (Metric::Meter(a), Metric::Meter(b)) => {
+            Arc::as_ptr(a) as *const () == Arc::as_ptr(b) as *const ()
+        }
+        (Metric::Gauge(a), Metric::Gauge(b)) => {
+            Arc::as_ptr(a) as *const () == Arc::as_ptr(b) as *const ()
+        }
+        (Metric::Histogram(a), Metric::Histogram(b)) => Arc::ptr_eq(a, b),
+        (Metric::Timer(a), Metric::Timer(b)) => Arc::ptr_eq(a, b),
+        _ => false,
+    }
+}
+
+/// The differences between two snapshots of the metrics in a registry.
+pub struct MetricsDiff<'a> {
+    added: Vec<(&'a MetricId, &'a Metric)>,
+    removed: Vec<(&'a MetricId, &'a Metric)>,
+    changed: Vec<(&'a MetricId, &'a Metric)>,
+}
+
+impl<'a> MetricsDiff<'a> {
+    /// Returns the metrics present in the new snapshot but not the previous one.
+    #[inline]
+    pub fn added(&self) -> &[(&'a MetricId, &'a Metric)] {
+        &self.added
+    }
+
+    /// Returns the metrics present in the previous snapshot but not the new one.
+    #[inline]
+    pub fn removed(&self) -> &[(&'a MetricId, &'a Metric)] {
+        &self.removed
+    }
+
+    /// Returns the metrics whose registered instance or value changed between the snapshots, as of the new snapshot.
+    #[inline]
+    pub fn changed(&self) -> &[(&'a MetricId, &'a Metric)] {
+        &self.changed
+    }
+
+    /// Returns `true` if the snapshots contain the same metrics.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
     }
 }
 
@@ -335,8 +794,10 @@ impl<'a> ExactSizeIterator for MetricsIter<'a> {}
 
 #[cfg(test)]
 mod test {
-    use crate::{MetricId, MetricRegistry};
+    use crate::{Metric, MetricId, MetricRegistry};
     use serde_value::Value;
+    use std::borrow::Cow;
+    use std::sync::Arc;
     use std::time::Duration;
 
     #[test]
@@ -368,6 +829,30 @@ mod test {
         assert_eq!(b.count(), 1);
     }
 
+    #[test]
+    fn get() {
+        let registry = MetricRegistry::new();
+
+        assert!(registry.get("counter").is_none());
+        registry.counter("counter").add(2);
+        match registry.get("counter") {
+            Some(Metric::Counter(counter)) => assert_eq!(counter.count(), 2),
+            _ => panic!("counter not registered"),
+        }
+        assert!(registry.get("other").is_none());
+
+        registry.gauge("gauge", || 3);
+        registry.timer("timer").update(Duration::from_nanos(10));
+        registry.timer("timer").update(Duration::from_nanos(20));
+
+        assert_metric!(registry, "counter", count == 2);
+        assert_metric!(registry, "gauge", value == Value::I32(3));
+        assert_metric!(registry, "timer", count >= 2);
+        assert_metric!(registry, "timer", min == 10);
+        assert_metric!(registry, "timer", max == 20);
+        assert_metric!(registry, "timer", mean > 10.);
+    }
+
     #[test]
     fn metrics_returns_snapshot() {
         let registry = MetricRegistry::new();
@@ -392,4 +877,188 @@ mod test {
         a.inc();
         assert_eq!(b.count(), 0);
     }
+
+    #[test]
+    fn cardinality_limit() {
+        let mut registry = MetricRegistry::new();
+        registry.counter(MetricId::new("requests").with_tag("user", "a"));
+        registry.set_cardinality_limit("requests", 2);
+
+        let b = registry.counter(MetricId::new("requests").with_tag("user", "b"));
+        let c = registry.counter(MetricId::new("requests").with_tag("user", "c"));
+        let d = registry.counter(MetricId::new("requests").with_tag("user", "d"));
+        b.inc();
+        c.inc();
+        d.inc();
+
+        let other = registry.counter(MetricId::new("requests").with_tag("user", "other"));
+        assert_eq!(other.count(), 2);
+        assert_eq!(registry.metrics().iter().len(), 3);
+
+        // existing tag sets are still returned
+        let b2 = registry.counter(MetricId::new("requests").with_tag("user", "b"));
+        assert_eq!(b2.count(), 1);
+
+        // removing metrics frees up space, though the `other` metric counts towards the limit
+        registry.remove(MetricId::new("requests").with_tag("user", "a"));
+        registry.remove(MetricId::new("requests").with_tag("user", "b"));
+        let e = registry.counter(MetricId::new("requests").with_tag("user", "e"));
+        assert_eq!(e.count(), 0);
+        assert_eq!(registry.metrics().iter().len(), 2);
+
+        // other names are unaffected
+        registry.counter(MetricId::new("other").with_tag("user", "a"));
+        registry.counter(MetricId::new("other").with_tag("user", "b"));
+        registry.counter(MetricId::new("other").with_tag("user", "c"));
+        assert_eq!(registry.metrics().iter().len(), 5);
+    }
+
+    #[test]
+    fn tag_normalizer() {
+        let mut registry = MetricRegistry::new();
+        registry.set_tag_normalizer(Arc::new(|_: &str, key: &str, value: &str| {
+            if key == "method" && value.chars().any(|c| c.is_ascii_lowercase()) {
+                Some(Cow::Owned(value.to_ascii_uppercase()))
+            } else {
+                None
+            }
+        }));
+
+        let a = registry.counter(MetricId::new("requests").with_tag("method", "get"));
+        let b = registry.counter(
+            MetricId::new("requests")
+                .with_tag("method", "GET")
+                .with_tag("path", "/foo"),
+        );
+        a.inc();
+        assert_eq!(
+            registry
+                .counter(MetricId::new("requests").with_tag("method", "GET"))
+                .count(),
+            1
+        );
+        assert_eq!(b.count(), 0);
+
+        assert!(registry
+            .remove(MetricId::new("requests").with_tag("method", "Get"))
+            .is_some());
+        assert_eq!(registry.metrics().iter().len(), 1);
+    }
+
+    #[test]
+    fn stats() {
+        let registry = MetricRegistry::new();
+
+        for i in 0..3 {
+            registry.counter(MetricId::new("requests").with_tag("user", i.to_string()));
+        }
+        registry.counter("counter");
+        registry.timer(MetricId::new("timer").with_tag("a", "b"));
+        registry.timer(MetricId::new("timer").with_tag("a", "c"));
+        registry.gauge("gauge", || 1);
+
+        let stats = registry.stats();
+        assert_eq!(stats.metrics(), 7);
+        assert_eq!(stats.counters(), 4);
+        assert_eq!(stats.meters(), 0);
+        assert_eq!(stats.gauges(), 1);
+        assert_eq!(stats.histograms(), 0);
+        assert_eq!(stats.timers(), 2);
+        assert_eq!(stats.names(), 4);
+        assert_eq!(
+            stats.highest_cardinality(2),
+            &[("requests".to_string(), 3), ("timer".to_string(), 2)],
+        );
+        assert_eq!(stats.highest_cardinality(10).len(), 4);
+    }
+
+    #[test]
+    fn diff() {
+        let registry = MetricRegistry::new();
+
+        registry.counter("counter").inc();
+        registry.gauge("gauge", || 1);
+        registry.timer("timer");
+
+        let ids = |metrics: &[(&MetricId, &Metric)]| {
+            let mut ids = metrics
+                .iter()
+                .map(|(id, _)| id.name().to_string())
+                .collect::<Vec<_>>();
+            ids.sort();
+            ids
+        };
+
+        let previous = registry.metrics();
+        registry.remove("missing");
+        assert!(registry.metrics().diff(&previous).is_empty());
+
+        registry.counter("counter").inc();
+        let metrics = registry.metrics();
+        let diff = metrics.diff(&previous);
+        assert!(diff.added().is_empty());
+        assert!(diff.removed().is_empty());
+        assert_eq!(ids(diff.changed()), &["counter"]);
+
+        registry.replace_gauge("gauge", || 1);
+        registry.remove("timer");
+        registry.meter("meter");
+
+        let metrics = registry.metrics();
+        let diff = metrics.diff(&previous);
+        assert_eq!(ids(diff.added()), &["meter"]);
+        assert_eq!(ids(diff.removed()), &["timer"]);
+        assert_eq!(ids(diff.changed()), &["counter", "gauge"]);
+    }
+
+    #[test]
+    fn timer_with_gauges() {
+        let registry = MetricRegistry::new();
+
+        let timer = registry.timer_with_gauges(MetricId::new("timer").with_tag("foo", "bar"));
+        timer.update(Duration::from_nanos(10));
+
+        let metrics = registry.metrics();
+        let mut names = metrics
+            .iter()
+            .inspect(|(id, _)| assert_eq!(id.tags().iter().collect::<Vec<_>>(), &[("foo", "bar")]))
+            .map(|(id, _)| id.name())
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        assert_eq!(
+            names,
+            &[
+                "timer",
+                "timer.max",
+                "timer.mean",
+                "timer.p50",
+                "timer.p95",
+                "timer.p99"
+            ],
+        );
+
+        let max = registry.gauge(MetricId::new("timer.max").with_tag("foo", "bar"), || 0);
+        assert_eq!(max.value(), Value::I64(10));
+    }
+
+    #[test]
+    fn f64_metrics() {
+        let registry = MetricRegistry::new();
+
+        registry.f64_counter("counter").add(1.5);
+        registry.f64_counter("counter").add(2.);
+        assert_eq!(registry.f64_counter("counter").count(), 3.5);
+
+        registry.f64_gauge("gauge").set(1.5);
+        assert_eq!(registry.f64_gauge("gauge").get(), 1.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn f64_gauge_type_mismatch() {
+        let registry = MetricRegistry::new();
+
+        registry.gauge("gauge", || 1);
+        registry.f64_gauge("gauge");
+    }
 }
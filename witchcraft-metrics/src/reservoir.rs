@@ -21,6 +21,15 @@ pub trait Reservoir: 'static + Sync + Send {
     /// Adds a value to the reservoir.
     fn update(&self, value: i64);
 
+    /// Adds `n` occurrences of a value to the reservoir.
+    ///
+    /// Defaults to calling [`Reservoir::update`] `n` times.
+    fn update_n(&self, value: i64, n: u64) {
+        for _ in 0..n {
+            self.update(value);
+        }
+    }
+
     /// Returns a snapshot of statistics about the values in the reservoir.
     fn snapshot(&self) -> Box<dyn Snapshot>;
 }
@@ -47,6 +56,46 @@ pub trait Snapshot: 'static + Sync + Send {
 
     /// Returns the standard deviation of the values in the snapshot.
     fn stddev(&self) -> f64;
+
+    /// Returns the number of values recorded by the reservoir as of the snapshot, if the reservoir tracks it.
+    ///
+    /// Statistics computed from only a few values can be misleading, so this can be used to suppress them until
+    /// enough values have been recorded.
+    ///
+    /// Defaults to `None`.
+    fn count(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns the cumulative bucket counts of the snapshot, if the reservoir tracks them.
+    ///
+    /// The buckets are sorted by upper bound, and the last bucket always has an upper bound of `i64::MAX`.
+    ///
+    /// Defaults to `None`.
+    fn buckets(&self) -> Option<&[Bucket]> {
+        None
+    }
+}
+
+/// A cumulative histogram bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bucket {
+    upper_bound: i64,
+    count: u64,
+}
+
+impl Bucket {
+    /// Returns the inclusive upper bound of the bucket.
+    #[inline]
+    pub fn upper_bound(&self) -> i64 {
+        self.upper_bound
+    }
+
+    /// Returns the number of values less than or equal to the bucket's upper bound.
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
 }
 
 /// A reservoir which exponentially weights in favor of recent values.
@@ -81,11 +130,202 @@ impl Reservoir for ExponentiallyDecayingReservoir {
         self.histogram.lock().update_at(self.clock.now(), value);
     }
 
+    /// Adds `n` occurrences of a value to the reservoir.
+    ///
+    /// The decaying histogram has no way to insert a weighted value, so this costs O(n). The lock is released
+    /// periodically so that concurrent updates aren't blocked for the entire call.
+    fn update_n(&self, value: i64, n: u64) {
+        const CHUNK: u64 = 1024;
+
+        let now = self.clock.now();
+        let mut remaining = n;
+        while remaining > 0 {
+            let chunk = remaining.min(CHUNK);
+            let mut histogram = self.histogram.lock();
+            for _ in 0..chunk {
+                histogram.update_at(now, value);
+            }
+            remaining -= chunk;
+        }
+    }
+
     fn snapshot(&self) -> Box<dyn Snapshot> {
         Box::new(self.histogram.lock().snapshot())
     }
 }
 
+struct BucketState {
+    counts: Vec<u64>,
+    count: u64,
+    sum: f64,
+    sum_squares: f64,
+    min: i64,
+    max: i64,
+}
+
+/// A reservoir which counts values into a fixed set of buckets.
+///
+/// Unlike the [`ExponentiallyDecayingReservoir`], it tracks every value ever recorded, and its snapshots report exact
+/// cumulative [`Bucket`] counts. Quantiles are estimated by the upper bound of the bucket containing them.
+pub struct BucketReservoir {
+    bounds: Vec<i64>,
+    state: Mutex<BucketState>,
+}
+
+impl BucketReservoir {
+    /// Creates a new reservoir with the specified bucket upper bounds.
+    ///
+    /// A final bucket with an upper bound of `i64::MAX` is added if not already present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bounds are not strictly increasing.
+    pub fn new(bounds: &[i64]) -> Self {
+        assert!(
+            bounds.windows(2).all(|w| w[0] < w[1]),
+            "bucket bounds must be strictly increasing",
+        );
+
+        let mut bounds = bounds.to_vec();
+        if bounds.last() != Some(&i64::MAX) {
+            bounds.push(i64::MAX);
+        }
+
+        BucketReservoir {
+            state: Mutex::new(BucketState {
+                counts: vec![0; bounds.len()],
+                count: 0,
+                sum: 0.,
+                sum_squares: 0.,
+                min: i64::MAX,
+                max: i64::MIN,
+            }),
+            bounds,
+        }
+    }
+}
+
+impl Reservoir for BucketReservoir {
+    fn update(&self, value: i64) {
+        self.update_n(value, 1);
+    }
+
+    fn update_n(&self, value: i64, n: u64) {
+        if n == 0 {
+            return;
+        }
+
+        let idx = match self.bounds.binary_search(&value) {
+            Ok(idx) | Err(idx) => idx,
+        };
+
+        let mut state = self.state.lock();
+        state.counts[idx] += n;
+        state.count += n;
+        state.sum += value as f64 * n as f64;
+        state.sum_squares += value as f64 * value as f64 * n as f64;
+        state.min = state.min.min(value);
+        state.max = state.max.max(value);
+    }
+
+    fn snapshot(&self) -> Box<dyn Snapshot> {
+        let state = self.state.lock();
+
+        let mut count = 0;
+        let buckets = self
+            .bounds
+            .iter()
+            .zip(&state.counts)
+            .map(|(&upper_bound, &n)| {
+                count += n;
+                Bucket { upper_bound, count }
+            })
+            .collect();
+
+        Box::new(BucketSnapshot {
+            buckets,
+            count: state.count,
+            sum: state.sum,
+            sum_squares: state.sum_squares,
+            min: state.min,
+            max: state.max,
+        })
+    }
+}
+
+struct BucketSnapshot {
+    buckets: Vec<Bucket>,
+    count: u64,
+    sum: f64,
+    sum_squares: f64,
+    min: i64,
+    max: i64,
+}
+
+impl Snapshot for BucketSnapshot {
+    fn value(&self, quantile: f64) -> f64 {
+        assert!(
+            (0. ..=1.).contains(&quantile),
+            "quantile must be between 0 and 1",
+        );
+
+        if self.count == 0 {
+            return 0.;
+        }
+
+        let rank = ((quantile * self.count as f64).ceil() as u64).max(1);
+        let upper_bound = self
+            .buckets
+            .iter()
+            .find(|b| b.count >= rank)
+            .map_or(self.max, |b| b.upper_bound);
+
+        upper_bound.max(self.min).min(self.max) as f64
+    }
+
+    fn max(&self) -> i64 {
+        if self.count == 0 {
+            0
+        } else {
+            self.max
+        }
+    }
+
+    fn min(&self) -> i64 {
+        if self.count == 0 {
+            0
+        } else {
+            self.min
+        }
+    }
+
+    fn mean(&self) -> f64 {
+        if self.count == 0 {
+            0.
+        } else {
+            self.sum / self.count as f64
+        }
+    }
+
+    fn stddev(&self) -> f64 {
+        if self.count == 0 {
+            return 0.;
+        }
+
+        let mean = self.mean();
+        let variance = self.sum_squares / self.count as f64 - mean * mean;
+        variance.max(0.).sqrt()
+    }
+
+    fn count(&self) -> Option<u64> {
+        Some(self.count)
+    }
+
+    fn buckets(&self) -> Option<&[Bucket]> {
+        Some(&self.buckets)
+    }
+}
+
 impl Snapshot for exponential_decay_histogram::Snapshot {
     fn value(&self, quantile: f64) -> f64 {
         self.value(quantile) as f64
@@ -106,12 +346,16 @@ impl Snapshot for exponential_decay_histogram::Snapshot {
     fn stddev(&self) -> f64 {
         self.stddev()
     }
+
+    fn count(&self) -> Option<u64> {
+        Some(self.count())
+    }
 }
 
 #[cfg(test)]
 #[allow(clippy::float_cmp)]
 mod test {
-    use crate::{ExponentiallyDecayingReservoir, Reservoir};
+    use crate::{BucketReservoir, ExponentiallyDecayingReservoir, Reservoir};
 
     #[test]
     fn exponential_basic() {
@@ -133,5 +377,43 @@ mod test {
         assert_eq!(snapshot.min(), 0);
         assert_eq!(snapshot.mean(), 1.25);
         assert!((snapshot.stddev() - 2.165).abs() < 0.0001);
+        assert_eq!(snapshot.count(), Some(20));
+    }
+
+    #[test]
+    fn bucket_basic() {
+        let reservoir = BucketReservoir::new(&[1, 5, 10]);
+
+        let snapshot = reservoir.snapshot();
+        assert_eq!(snapshot.value(0.5), 0.);
+        assert_eq!(snapshot.max(), 0);
+        assert_eq!(snapshot.buckets().unwrap().last().unwrap().count(), 0);
+
+        for _ in 0..15 {
+            reservoir.update(0);
+        }
+
+        reservoir.update_n(5, 5);
+        reservoir.update_n(7, 0);
+
+        reservoir.update(20);
+
+        let snapshot = reservoir.snapshot();
+
+        let buckets = snapshot
+            .buckets()
+            .unwrap()
+            .iter()
+            .map(|b| (b.upper_bound(), b.count()))
+            .collect::<Vec<_>>();
+        assert_eq!(buckets, &[(1, 15), (5, 20), (10, 20), (i64::MAX, 21)]);
+
+        assert_eq!(snapshot.value(0.5), 1.);
+        assert_eq!(snapshot.value(0.9), 5.);
+        assert_eq!(snapshot.value(1.), 20.);
+        assert_eq!(snapshot.max(), 20);
+        assert_eq!(snapshot.min(), 0);
+        assert_eq!(snapshot.mean(), 45. / 21.);
+        assert_eq!(snapshot.count(), Some(21));
     }
 }
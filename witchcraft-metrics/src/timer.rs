@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use crate::{Clock, ExponentiallyDecayingReservoir, Meter, Reservoir, Snapshot};
+use std::convert::TryFrom;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -65,6 +66,44 @@ impl Timer {
         self.reservoir.update(nanos);
     }
 
+    /// Adds `n` timed events with the same duration to the metric.
+    ///
+    /// This is equivalent to calling [`Timer::update`] `n` times. Like
+    /// [`Histogram::update_n`](crate::Histogram::update_n), its cost depends on the reservoir.
+    #[inline]
+    pub fn update_n(&self, duration: Duration, n: u64) {
+        self.meter.mark(i64::try_from(n).unwrap_or(i64::MAX));
+        let nanos = duration.as_nanos() as i64;
+        self.reservoir.update_n(nanos, n);
+    }
+
+    /// Adds a new timed event to the metric, correcting for coordinated omission.
+    ///
+    /// If the event's duration exceeds `expected_interval`, the interval between events expected in the absence of
+    /// stalls, synthetic durations of `duration - expected_interval`, `duration - 2 * expected_interval`, and so on down to
+    /// `expected_interval` are also added to the timer's reservoir. These represent the events which would have been
+    /// delayed by the stall had they not been blocked from starting. The synthetic durations do not affect the timer's
+    /// count or rates.
+    ///
+    /// This is equivalent to [`Timer::update`] if `expected_interval` is zero. Note that the cost of this method is
+    /// proportional to `duration / expected_interval`.
+    pub fn update_with_expected_interval(&self, duration: Duration, expected_interval: Duration) {
+        self.update(duration);
+
+        if expected_interval == Duration::from_secs(0) {
+            return;
+        }
+
+        let mut missing = duration.checked_sub(expected_interval);
+        while let Some(value) = missing {
+            if value < expected_interval {
+                break;
+            }
+            self.reservoir.update(value.as_nanos() as i64);
+            missing = value.checked_sub(expected_interval);
+        }
+    }
+
     /// Returns a guard type which reports the time elapsed since its creation when it drops.
     #[inline]
     pub fn time(&self) -> Time<'_> {
@@ -104,6 +143,14 @@ impl Timer {
         self.meter.mean_rate()
     }
 
+    /// Returns `true` if the timer's rolling average rates cover at least `window` of history.
+    ///
+    /// See [`Meter::warmed_up`] for details.
+    #[inline]
+    pub fn warmed_up(&self, window: Duration) -> bool {
+        self.meter.warmed_up(window)
+    }
+
     /// Returns a snapshot of the statistical distribution of durations of events, measured in nanoseconds.
     #[inline]
     pub fn snapshot(&self) -> Box<dyn Snapshot> {
@@ -124,6 +171,20 @@ impl Drop for Time<'_> {
     }
 }
 
+/// Converts a floating point duration in a unit of `nanos_per_unit` nanoseconds to a `Duration`.
+///
+/// Returns `None` if the value is negative or NaN.
+#[cfg(any(feature = "metrics", feature = "opentelemetry"))]
+pub(crate) fn f64_duration(value: f64, nanos_per_unit: f64) -> Option<Duration> {
+    let nanos = value * nanos_per_unit;
+    if nanos >= 0. {
+        // float to int casts saturate
+        Some(Duration::from_nanos(nanos.round() as u64))
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::Timer;
@@ -148,6 +209,30 @@ mod test {
         assert_eq!(timer.snapshot().value(0.8), 5.)
     }
 
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn expected_interval() {
+        let timer = Timer::default();
+
+        timer.update_with_expected_interval(Duration::from_nanos(5), Duration::from_nanos(10));
+        assert_eq!(timer.count(), 1);
+        assert_eq!(timer.snapshot().mean(), 5.);
+
+        let timer = Timer::default();
+
+        timer.update_with_expected_interval(Duration::from_nanos(45), Duration::from_nanos(10));
+        assert_eq!(timer.count(), 1);
+        let snapshot = timer.snapshot();
+        assert_eq!(snapshot.max(), 45);
+        assert_eq!(snapshot.min(), 15);
+        assert_eq!(snapshot.mean(), 30.);
+
+        let timer = Timer::default();
+
+        timer.update_with_expected_interval(Duration::from_nanos(45), Duration::from_nanos(0));
+        assert_eq!(timer.snapshot().min(), 45);
+    }
+
     #[test]
     fn time() {
         let timer = Timer::default();